@@ -1,6 +1,6 @@
 use std::fmt;
-use std::io::{BufReader, Read};
-use std::path::Path;
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use std::path::{Component, Path};
 use std::str::FromStr;
 
 #[cfg(feature = "bzip2")]
@@ -10,9 +10,154 @@ use flate2::read::GzDecoder;
 use xz::bufread::XzDecoder;
 #[cfg(feature = "xz")]
 use xz::stream::Stream as XzStream;
+use zip::result::ZipError;
 use zip::ZipArchive;
 
-use crate::{Error, Metadata};
+use crate::egg::parse_requires_txt;
+use crate::hash::Hasher;
+use crate::name;
+use crate::{
+    EggFilename, EntryPoints, Error, Metadata, Record, RecordVerification, Requirement, Version,
+    WheelFilename, WheelInfo,
+};
+
+/// Python version tag of an egg, e.g. `py3.9` for `foo-1.0-py3.9.egg`
+fn egg_python_version(egg_filename: Option<&EggFilename>) -> String {
+    match egg_filename.and_then(|egg_filename| egg_filename.pyver.as_ref()) {
+        Some(pyver) => format!("py{pyver}"),
+        None => "any".to_string(),
+    }
+}
+
+/// Returns the final component of `path` as the distribution filename
+fn file_name(path: &Path) -> Option<String> {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .map(ToString::to_string)
+}
+
+/// Parse a `major.minor` Python version, e.g. the `3.9` of an egg filename
+fn parse_major_minor(version: &str) -> Option<(u64, u64)> {
+    let (major, minor) = version.split_once('.')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+/// Returns `true` if a wheel tagged with `python_tag` and `abi_tag` can be installed on
+/// the Python version `major.minor`, tags that aren't understood are assumed compatible
+///
+/// `py3` matches any 3.x, `py38` and `cp38-abi3` also match later 3.x versions, other
+/// interpreter specific tags such as `cp38-cp38` only match 3.8.
+fn python_tag_supports(python_tag: &str, abi_tag: &str, (major, minor): (u64, u64)) -> bool {
+    let Some(position) = python_tag.find(|c: char| c.is_ascii_digit()) else {
+        return true;
+    };
+    let (interpreter, digits) = python_tag.split_at(position);
+    // The first digit is the major version, the rest is the minor version
+    let (Ok(tag_major), tag_minor) = (digits[..1].parse::<u64>(), &digits[1..]) else {
+        return true;
+    };
+    if tag_major != major {
+        return false;
+    }
+    if tag_minor.is_empty() {
+        return true;
+    }
+    let Ok(tag_minor) = tag_minor.parse::<u64>() else {
+        return true;
+    };
+    if interpreter == "py" || abi_tag == "abi3" {
+        tag_minor <= minor
+    } else {
+        tag_minor == minor
+    }
+}
+
+/// Pick the metadata file of a source distribution among all `PKG-INFO` files
+///
+/// A single file is used as is, otherwise the only top-level `PKG-INFO` wins, then the
+/// only `.egg-info/PKG-INFO`, so the order of files in the archive never matters.
+/// `top_level_depth` is the number of path components of the top-level `PKG-INFO`,
+/// e.g. 2 for `foo-1.0/PKG-INFO` in an archive and 1 in an unpacked directory.
+fn select_metadata_file(
+    metadata_files: Vec<String>,
+    top_level_depth: usize,
+) -> Result<String, Error> {
+    if let [metadata_file] = metadata_files.as_slice() {
+        return Ok(metadata_file.clone());
+    }
+    if metadata_files.is_empty() {
+        return Err(Error::MetadataNotFound);
+    }
+    // Archives created with e.g. `tar -czf foo-1.0.tar.gz ./foo-1.0` prefix every path with `./`
+    let depth = |f: &str| {
+        Path::new(f)
+            .components()
+            .filter(|component| *component != Component::CurDir)
+            .count()
+    };
+    let top_level_files: Vec<_> = metadata_files
+        .iter()
+        .filter(|f| depth(f) == top_level_depth)
+        .collect();
+    if let [metadata_file] = top_level_files.as_slice() {
+        return Ok(metadata_file.to_string());
+    }
+    let egg_info_files: Vec<_> = metadata_files
+        .iter()
+        .filter(|f| f.ends_with(".egg-info/PKG-INFO"))
+        .collect();
+    if let [metadata_file] = egg_info_files.as_slice() {
+        return Ok(metadata_file.to_string());
+    }
+    Err(Error::MultipleMetadataFiles(metadata_files))
+}
+
+/// Find the `PKG-INFO` files of an unpacked source distribution
+///
+/// Only `PKG-INFO`, `*.egg-info/PKG-INFO` and `src/*.egg-info/PKG-INFO` are considered,
+/// the rest of the tree may contain virtual environments or vendored packages.
+fn find_directory_metadata_files(root: &Path) -> Result<Vec<String>, Error> {
+    let mut metadata_files = Vec::new();
+    if root.join("PKG-INFO").is_file() {
+        metadata_files.push("PKG-INFO".to_string());
+    }
+    for dir in ["", "src"] {
+        let Ok(entries) = fs_err::read_dir(root.join(dir)) else {
+            continue;
+        };
+        for entry in entries {
+            let name = entry?.file_name();
+            let Some(name) = name.to_str() else {
+                continue;
+            };
+            if name.ends_with(".egg-info") && root.join(dir).join(name).join("PKG-INFO").is_file() {
+                let path = if dir.is_empty() {
+                    format!("{name}/PKG-INFO")
+                } else {
+                    format!("{dir}/{name}/PKG-INFO")
+                };
+                metadata_files.push(path);
+            }
+        }
+    }
+    metadata_files.sort();
+    Ok(metadata_files)
+}
+
+/// Source distribution file extensions
+const SDIST_EXTENSIONS: &[&str] = &[
+    ".tar.lzma",
+    ".tar.bz2",
+    ".tar.gz",
+    ".tar.lz",
+    ".tar.xz",
+    ".tar",
+    ".tbz",
+    ".tgz",
+    ".tlz",
+    ".txz",
+    ".zip",
+];
 
 /// Python package distribution type
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -43,6 +188,15 @@ pub struct Distribution {
     dist_type: DistributionType,
     metadata: Metadata,
     python_version: String,
+    filename: Option<String>,
+    metadata_file: Option<Vec<u8>>,
+    wheel_filename: Option<WheelFilename>,
+    wheel_info: Option<WheelInfo>,
+    record: Option<Record>,
+    record_verification: Option<RecordVerification>,
+    entry_points: Option<EntryPoints>,
+    egg_filename: Option<EggFilename>,
+    egg_requirements: Option<Vec<Requirement>>,
 }
 
 impl fmt::Display for DistributionType {
@@ -74,64 +228,284 @@ impl FromStr for SDistType {
     }
 }
 
+impl SDistType {
+    /// Detect the archive format from the magic bytes at the start of `reader`
+    ///
+    /// The reader is rewound to its original position afterwards.
+    fn sniff<R: Read + Seek>(reader: &mut R) -> Result<Option<Self>, Error> {
+        let start = reader.stream_position()?;
+        let header = Self::read_header(reader)?;
+        reader.seek(SeekFrom::Start(start))?;
+        Self::sniff_bytes(&header)
+    }
+
+    /// Read the leading bytes of `reader` needed by [`SDistType::sniff_bytes`]
+    fn read_header<R: Read>(reader: &mut R) -> Result<Vec<u8>, Error> {
+        // A tar header block is 512 bytes, the `ustar` magic lives at offset 257
+        let mut buf = Vec::with_capacity(512);
+        reader.take(512).read_to_end(&mut buf)?;
+        Ok(buf)
+    }
+
+    /// Detect the archive format from the magic bytes at the start of `buf`
+    fn sniff_bytes(buf: &[u8]) -> Result<Option<Self>, Error> {
+        let sdist_type = if buf.starts_with(b"PK\x03\x04") || buf.starts_with(b"PK\x05\x06") {
+            SDistType::Zip
+        } else if buf.starts_with(b"\x1f\x8b") {
+            SDistType::GzTar
+        } else if buf.starts_with(b"BZh") {
+            #[cfg(feature = "bzip2")]
+            {
+                SDistType::BzTar
+            }
+            #[cfg(not(feature = "bzip2"))]
+            return Err(Error::UnknownDistributionType);
+        } else if buf.starts_with(b"\xfd7zXZ\x00") || buf.starts_with(b"\x5d\x00\x00") {
+            #[cfg(feature = "xz")]
+            {
+                SDistType::XzTar
+            }
+            #[cfg(not(feature = "xz"))]
+            return Err(Error::UnknownDistributionType);
+        } else if buf.get(257..262) == Some(b"ustar") {
+            #[cfg(feature = "deprecated-formats")]
+            {
+                SDistType::Tar
+            }
+            #[cfg(not(feature = "deprecated-formats"))]
+            return Err(Error::UnknownDistributionType);
+        } else {
+            return Ok(None);
+        };
+        Ok(Some(sdist_type))
+    }
+}
+
 impl Distribution {
     /// Open and parse a distribution from `path`
+    ///
+    /// A directory is read as an unpacked source distribution from its `PKG-INFO`, picked
+    /// like in sdist archives, falling back to the `[project]` table of `pyproject.toml`.
     pub fn new(path: impl AsRef<Path>) -> Result<Self, Error> {
-        let path = path.as_ref();
-        let ext = path
-            .extension()
-            .and_then(|ext| ext.to_str())
-            .ok_or(Error::UnknownDistributionType)?;
-
-        Ok(if let Ok(sdist_type) = ext.parse() {
-            Self {
-                dist_type: DistributionType::SDist,
-                metadata: Self::parse_sdist(path, sdist_type)?,
-                python_version: "source".to_string(),
+        Self::open(path.as_ref(), false)
+    }
+
+    /// Open and parse a distribution from `path`, verifying every file of a wheel against
+    /// its `RECORD` file
+    ///
+    /// Like [`Distribution::new`], but the files are hashed while the archive is open, the
+    /// result is available from [`Distribution::record_verification`]. A wheel without a
+    /// `RECORD` file fails with [`Error::RecordNotFound`] and a malformed one is an error
+    /// instead of being ignored. Other distribution types are not verified.
+    pub fn new_verified(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::open(path.as_ref(), true)
+    }
+
+    fn open(path: &Path, verify: bool) -> Result<Self, Error> {
+        if path.is_dir() {
+            return Self::parse_directory(path);
+        }
+        let reader = BufReader::new(fs_err::File::open(path)?);
+        Self::read(reader, path, verify)
+    }
+
+    /// Parse a distribution from `reader`
+    ///
+    /// The archive format is detected from the leading bytes of `reader`, `filename` is only
+    /// used as a hint to determine the distribution type and Python version tag,
+    /// the file itself is never opened.
+    ///
+    /// A `filename` ending in `.metadata`, e.g. `foo-1.0-py3-none-any.whl.metadata`,
+    /// is read as a standalone core metadata file served by package indexes following
+    /// [PEP 658](https://peps.python.org/pep-0658/).
+    pub fn from_reader<R: Read + Seek>(
+        reader: R,
+        filename: impl AsRef<Path>,
+    ) -> Result<Self, Error> {
+        Self::read(reader, filename.as_ref(), false)
+    }
+
+    /// Parse a distribution from `reader`, verifying every file of a wheel against its
+    /// `RECORD` file, see [`Distribution::new_verified`]
+    pub fn from_reader_verified<R: Read + Seek>(
+        reader: R,
+        filename: impl AsRef<Path>,
+    ) -> Result<Self, Error> {
+        Self::read(reader, filename.as_ref(), true)
+    }
+
+    /// Parse a distribution from a `reader` that can't seek, e.g. an HTTP response body
+    ///
+    /// Tar based source distributions are read in a single pass, keeping only the `PKG-INFO`
+    /// files in memory. Zip archives such as wheels and eggs have their index at the end, so
+    /// they are read into memory first and parsed like with [`Distribution::from_reader`].
+    pub fn from_stream<R: Read>(mut reader: R, filename: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = filename.as_ref();
+        let mut header = SDistType::read_header(&mut reader)?;
+        match SDistType::sniff_bytes(&header)? {
+            Some(SDistType::Zip) | None => {
+                reader.read_to_end(&mut header)?;
+                Self::from_reader(io::Cursor::new(header), path)
             }
-        } else {
-            match ext {
-                "egg" => {
-                    let parts: Vec<&str> = path
-                        .file_stem()
-                        .unwrap()
-                        .to_str()
-                        .unwrap()
-                        .split('-')
-                        .collect();
-                    let python_version = match parts.as_slice() {
-                        [_name, _version, py_ver] => py_ver,
-                        _ => "any",
-                    };
-                    Self {
-                        dist_type: DistributionType::Egg,
-                        metadata: Self::parse_egg(path)?,
-                        python_version: python_version.to_string(),
-                    }
+            Some(sdist_type) => {
+                let reader = io::Cursor::new(header).chain(reader);
+                Ok(Self {
+                    filename: file_name(path),
+                    ..Self::from_parts(
+                        DistributionType::SDist,
+                        Self::parse_tar_sdist(reader, sdist_type)?,
+                        "source",
+                    )
+                })
+            }
+        }
+    }
+
+    fn read<R: Read + Seek>(reader: R, path: &Path, verify: bool) -> Result<Self, Error> {
+        Ok(Self {
+            filename: file_name(path),
+            ..Self::parse_reader(reader, path, verify)?
+        })
+    }
+
+    fn parse_reader<R: Read + Seek>(
+        mut reader: R,
+        path: &Path,
+        verify: bool,
+    ) -> Result<Self, Error> {
+        let ext = path.extension().and_then(|ext| ext.to_str());
+        if ext == Some("metadata") {
+            return Self::parse_metadata_file(reader, path);
+        }
+        let sdist_type = match SDistType::sniff(&mut reader)? {
+            Some(sdist_type) => sdist_type,
+            // Fall back to the file extension, e.g. for pre-POSIX tar archives
+            None => match ext {
+                Some("egg" | "whl") => SDistType::Zip,
+                Some(ext) => ext.parse()?,
+                None => return Err(Error::UnknownDistributionType),
+            },
+        };
+
+        let archive = match sdist_type {
+            SDistType::Zip => ZipArchive::new(reader)?,
+            _ => {
+                return Ok(Self::from_parts(
+                    DistributionType::SDist,
+                    Self::parse_sdist(reader, sdist_type)?,
+                    "source",
+                ))
+            }
+        };
+        let dist_type = match ext {
+            Some("egg") => DistributionType::Egg,
+            Some("whl") => DistributionType::Wheel,
+            _ => Self::detect_zip_type(&archive),
+        };
+        Ok(match dist_type {
+            DistributionType::SDist => {
+                Self::from_parts(dist_type, Self::parse_zip(archive, "PKG-INFO")?, "source")
+            }
+            DistributionType::Egg => {
+                let egg_filename = match ext {
+                    Some("egg") => path
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .and_then(|name| name.parse::<EggFilename>().ok()),
+                    _ => None,
+                };
+                Self::parse_egg(archive, egg_filename)?
+            }
+            DistributionType::Wheel => {
+                // A renamed wheel, e.g. `build.whl`, is still read, only without a parsed filename
+                let wheel_filename = match ext {
+                    Some("whl") => path
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .and_then(|name| name.parse::<WheelFilename>().ok()),
+                    _ => None,
+                };
+                Self::parse_wheel(archive, wheel_filename, verify)?
+            }
+        })
+    }
+
+    /// Read a standalone metadata file, the distribution type is inferred from the
+    /// filename of the distribution it belongs to
+    fn parse_metadata_file<R: Read>(mut reader: R, path: &Path) -> Result<Self, Error> {
+        let mut content = Vec::new();
+        reader.read_to_end(&mut content)?;
+        let metadata = Metadata::parse(&content)?;
+        let dist_path = Path::new(path.file_stem().unwrap_or_default());
+        let dist = match dist_path.extension().and_then(|ext| ext.to_str()) {
+            Some("whl") => {
+                let wheel_filename: Option<WheelFilename> =
+                    dist_path.to_str().and_then(|name| name.parse().ok());
+                let python_version = match &wheel_filename {
+                    Some(wheel_filename) => wheel_filename.python_tags.join("."),
+                    None => "any".to_string(),
+                };
+                Self {
+                    wheel_filename,
+                    ..Self::from_parts(DistributionType::Wheel, metadata, python_version)
                 }
-                "whl" => {
-                    let parts: Vec<&str> = path
-                        .file_stem()
-                        .unwrap()
-                        .to_str()
-                        .unwrap()
-                        .split('-')
-                        .collect();
-                    let python_version = match parts.as_slice() {
-                        [_name, _version, py_ver, _abi_tag, _plat_tag] => py_ver,
-                        _ => "any",
-                    };
-                    Self {
-                        dist_type: DistributionType::Wheel,
-                        metadata: Self::parse_wheel(path)?,
-                        python_version: python_version.to_string(),
-                    }
+            }
+            Some("egg") => {
+                let egg_filename: Option<EggFilename> =
+                    dist_path.to_str().and_then(|name| name.parse().ok());
+                let python_version = egg_python_version(egg_filename.as_ref());
+                Self {
+                    egg_filename,
+                    ..Self::from_parts(DistributionType::Egg, metadata, python_version)
                 }
-                _ => return Err(Error::UnknownDistributionType),
             }
+            _ => Self::from_parts(DistributionType::SDist, metadata, "source"),
+        };
+        Ok(Self {
+            metadata_file: Some(content),
+            ..dist
         })
     }
 
+    /// Read an unpacked source distribution or project directory
+    fn parse_directory(path: &Path) -> Result<Self, Error> {
+        let metadata_files = find_directory_metadata_files(path)?;
+        let pyproject_toml = path.join("pyproject.toml");
+        let metadata = match select_metadata_file(metadata_files, 1) {
+            Ok(metadata_file) => Metadata::parse(&fs_err::read(path.join(metadata_file))?)?,
+            Err(Error::MetadataNotFound) if pyproject_toml.is_file() => {
+                Metadata::from_pyproject_path(pyproject_toml)?
+            }
+            Err(err) => return Err(err),
+        };
+        Ok(Self::from_parts(
+            DistributionType::SDist,
+            metadata,
+            "source",
+        ))
+    }
+
+    fn from_parts(
+        dist_type: DistributionType,
+        metadata: Metadata,
+        python_version: impl Into<String>,
+    ) -> Self {
+        Self {
+            dist_type,
+            metadata,
+            python_version: python_version.into(),
+            filename: None,
+            metadata_file: None,
+            wheel_filename: None,
+            wheel_info: None,
+            record: None,
+            record_verification: None,
+            entry_points: None,
+            egg_filename: None,
+            egg_requirements: None,
+        }
+    }
+
     /// Returns distribution type
     pub fn r#type(&self) -> DistributionType {
         self.dist_type
@@ -149,95 +523,462 @@ impl Distribution {
         &self.python_version
     }
 
-    fn parse_sdist(path: &Path, sdist_type: SDistType) -> Result<Metadata, Error> {
-        match sdist_type {
-            SDistType::Zip => Self::parse_zip(path, "PKG-INFO"),
-            SDistType::GzTar => {
-                Self::parse_tar(GzDecoder::new(BufReader::new(fs_err::File::open(path)?)))
+    /// Returns the filename the distribution was opened from
+    ///
+    /// `None` for directories, their names don't follow a naming convention
+    pub fn filename(&self) -> Option<&str> {
+        self.filename.as_deref()
+    }
+
+    /// Verify a standalone `.metadata` file against the hash advertised by a package index
+    ///
+    /// `hash` is the value of the PEP 714 `data-core-metadata` attribute,
+    /// e.g. `sha256=<hex digest>`. A bare `true` advertises the file without a hash, so
+    /// there is nothing to verify and `Ok(true)` is returned. Returns
+    /// [`Error::MetadataNotFound`] if the distribution wasn't read from a `.metadata` file
+    /// and [`Error::UnsupportedHashAlgorithm`] for `hashlib` algorithms other than
+    /// `sha256`, `sha384` and `sha512`.
+    pub fn verify_metadata_hash(&self, hash: &str) -> Result<bool, Error> {
+        let content = self.metadata_file.as_ref().ok_or(Error::MetadataNotFound)?;
+        if hash == "true" {
+            return Ok(true);
+        }
+        let invalid = || Error::InvalidHash(hash.to_string());
+        let (algorithm, expected) = hash.split_once('=').ok_or_else(invalid)?;
+        let mut hasher = match Hasher::new(algorithm) {
+            Some(hasher) => hasher,
+            None if Hasher::is_hashlib_algorithm(algorithm) => {
+                return Err(Error::UnsupportedHashAlgorithm(algorithm.to_string()))
             }
+            None => return Err(invalid()),
+        };
+        hasher.update(content);
+        let actual: String = hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        if expected.len() != actual.len() || !expected.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        Ok(actual.eq_ignore_ascii_case(expected))
+    }
+
+    /// Check that the name and version in the filename match the metadata
+    ///
+    /// Names are compared after PEP 503 normalization and versions after PEP 440
+    /// normalization, versions that aren't PEP 440 are compared as written. Returns
+    /// [`Error::FilenameMismatch`] on mismatch and [`Error::InvalidFilename`] if a source
+    /// distribution filename has no version.
+    ///
+    /// Nothing is checked for distributions without a [`Distribution::filename`], i.e.
+    /// unpacked source distributions and project directories, these always pass.
+    pub fn check_filename(&self) -> Result<(), Error> {
+        let Some(filename) = &self.filename else {
+            return Ok(());
+        };
+        let dist_filename = filename.strip_suffix(".metadata").unwrap_or(filename);
+        let (name, version) = match self.dist_type {
+            DistributionType::Wheel => match &self.wheel_filename {
+                Some(wheel_filename) => (
+                    wheel_filename.name.to_string(),
+                    wheel_filename.version.clone(),
+                ),
+                None => {
+                    let wheel_filename: WheelFilename = dist_filename.parse()?;
+                    (wheel_filename.name.to_string(), wheel_filename.version)
+                }
+            },
+            // `{name}-{version}(-py{python version}(-{platform})?)?.egg`
+            DistributionType::Egg => match &self.egg_filename {
+                Some(egg_filename) => (egg_filename.name.to_string(), egg_filename.version.clone()),
+                None => {
+                    let egg_filename: EggFilename = dist_filename.parse()?;
+                    (egg_filename.name.to_string(), egg_filename.version)
+                }
+            },
+            // `{name}-{version}.{extension}`, legacy names may contain `-`
+            DistributionType::SDist => {
+                let stem = SDIST_EXTENSIONS
+                    .iter()
+                    .find_map(|ext| dist_filename.strip_suffix(ext))
+                    .unwrap_or(dist_filename);
+                let (name, version) =
+                    stem.rsplit_once('-')
+                        .ok_or_else(|| Error::InvalidFilename {
+                            filename: filename.clone(),
+                            reason: "expected `{name}-{version}`".to_string(),
+                        })?;
+                (name.to_string(), version.to_string())
+            }
+        };
+
+        let mismatch =
+            |field, filename_value: &str, metadata_value: &str| Error::FilenameMismatch {
+                filename: filename.clone(),
+                field,
+                filename_value: filename_value.to_string(),
+                metadata_value: metadata_value.to_string(),
+            };
+        if name::normalize(&name) != name::normalize(&self.metadata.name) {
+            return Err(mismatch("name", &name, &self.metadata.name));
+        }
+        let versions_match = match (
+            version.parse::<Version>(),
+            self.metadata.version.parse::<Version>(),
+        ) {
+            (Ok(filename_version), Ok(metadata_version)) => filename_version == metadata_version,
+            _ => version == self.metadata.version,
+        };
+        if !versions_match {
+            return Err(mismatch("version", &version, &self.metadata.version));
+        }
+        Ok(())
+    }
+
+    /// Returns the PEP 503 normalized distribution name, e.g. `foo-bar` for `Foo_Bar`
+    pub fn normalized_name(&self) -> String {
+        name::normalize(&self.metadata.name)
+    }
+
+    /// Returns `true` if the distribution can be installed on the given Python version
+    ///
+    /// Checks `Requires-Python`, the Python tags of wheels and the Python version of eggs,
+    /// a distribution without any of them supports any version. Platforms aren't checked.
+    pub fn supports_python(&self, python_version: &Version) -> Result<bool, Error> {
+        let requires_python = match self.metadata.requires_python_specifiers()? {
+            Some(specifiers) => specifiers.contains(python_version),
+            None => true,
+        };
+        let major = python_version.release.first().copied().unwrap_or_default();
+        let minor = python_version.release.get(1).copied().unwrap_or_default();
+        Ok(requires_python && self.tags_support_python((major, minor)))
+    }
+
+    /// Returns `true` if the wheel tags or the egg Python version allow `major.minor`
+    fn tags_support_python(&self, python_version: (u64, u64)) -> bool {
+        if let Some(egg_filename) = &self.egg_filename {
+            return match egg_filename.pyver.as_deref().and_then(parse_major_minor) {
+                Some(pyver) => pyver == python_version,
+                None => true,
+            };
+        }
+        let tags: Vec<(&str, &str)> = match (&self.wheel_filename, &self.wheel_info) {
+            (Some(wheel_filename), _) => wheel_filename
+                .python_tags
+                .iter()
+                .flat_map(|python_tag| {
+                    wheel_filename
+                        .abi_tags
+                        .iter()
+                        .map(move |abi_tag| (python_tag.as_str(), abi_tag.as_str()))
+                })
+                .collect(),
+            (None, Some(wheel_info)) => wheel_info
+                .tags
+                .iter()
+                .filter_map(|tag| {
+                    let mut parts = tag.split('-');
+                    Some((parts.next()?, parts.next()?))
+                })
+                .collect(),
+            (None, None) => return true,
+        };
+        tags.is_empty()
+            || tags.iter().any(|(python_tag, abi_tag)| {
+                python_tag_supports(python_tag, abi_tag, python_version)
+            })
+    }
+
+    /// Returns the entry points declared in `entry_points.txt` of a wheel or egg
+    pub fn entry_points(&self) -> Option<&EntryPoints> {
+        self.entry_points.as_ref()
+    }
+
+    /// Returns the parsed wheel filename
+    ///
+    /// Only available for wheels whose filename ends with `.whl` and follows the wheel
+    /// filename convention
+    pub fn wheel_filename(&self) -> Option<&WheelFilename> {
+        self.wheel_filename.as_ref()
+    }
+
+    /// Returns the parsed egg filename
+    ///
+    /// Only available for eggs whose filename ends with `.egg` and follows the egg
+    /// filename convention
+    pub fn egg_filename(&self) -> Option<&EggFilename> {
+        self.egg_filename.as_ref()
+    }
+
+    /// Returns the requirements declared in `requires.txt` and `depends.txt` of an egg
+    ///
+    /// Old eggs often only list their dependencies there and not as `Requires-Dist`
+    /// in `PKG-INFO`, extras and markers of sections are merged into each requirement.
+    pub fn egg_requirements(&self) -> Option<&[Requirement]> {
+        self.egg_requirements.as_deref()
+    }
+
+    /// Returns the parsed `WHEEL` file of a wheel, `None` if it's missing or malformed
+    pub fn wheel_info(&self) -> Option<&WheelInfo> {
+        self.wheel_info.as_ref()
+    }
+
+    /// Returns the parsed `RECORD` file of a wheel, `None` if it's missing or malformed
+    pub fn record(&self) -> Option<&Record> {
+        self.record.as_ref()
+    }
+
+    /// Returns the result of verifying a wheel against its `RECORD` file, only set for wheels
+    /// opened with [`Distribution::new_verified`] or [`Distribution::from_reader_verified`]
+    pub fn record_verification(&self) -> Option<&RecordVerification> {
+        self.record_verification.as_ref()
+    }
+
+    fn parse_sdist<R: Read + Seek>(reader: R, sdist_type: SDistType) -> Result<Metadata, Error> {
+        match sdist_type {
+            SDistType::Zip => Self::parse_zip(ZipArchive::new(reader)?, "PKG-INFO"),
+            _ => Self::parse_tar_sdist(reader, sdist_type),
+        }
+    }
+
+    /// Read a tar based source distribution in a single pass
+    fn parse_tar_sdist<R: Read>(reader: R, sdist_type: SDistType) -> Result<Metadata, Error> {
+        match sdist_type {
+            SDistType::Zip => unreachable!("zip archives need to seek"),
+            SDistType::GzTar => Self::parse_tar(GzDecoder::new(reader)),
             #[cfg(feature = "deprecated-formats")]
-            SDistType::Tar => Self::parse_tar(BufReader::new(fs_err::File::open(path)?)),
+            SDistType::Tar => Self::parse_tar(reader),
             #[cfg(feature = "bzip2")]
-            SDistType::BzTar => {
-                Self::parse_tar(BzDecoder::new(BufReader::new(fs_err::File::open(path)?)))
-            }
+            SDistType::BzTar => Self::parse_tar(BzDecoder::new(reader)),
             #[cfg(feature = "xz")]
             SDistType::XzTar => Self::parse_tar(XzDecoder::new_stream(
-                BufReader::new(fs_err::File::open(path)?),
+                BufReader::new(reader),
                 XzStream::new_auto_decoder(u64::MAX, 0).unwrap(),
             )),
         }
     }
 
-    fn parse_egg(path: &Path) -> Result<Metadata, Error> {
-        Self::parse_zip(path, "EGG-INFO/PKG-INFO")
+    fn parse_egg<R: Read + Seek>(
+        mut archive: ZipArchive<R>,
+        egg_filename: Option<EggFilename>,
+    ) -> Result<Self, Error> {
+        let metadata_file = Self::find_zip_metadata_file(&archive, "EGG-INFO/PKG-INFO")?;
+        let metadata = Metadata::parse(&Self::read_zip_file(&mut archive, &metadata_file)?)?;
+        let entry_points = Self::read_optional_zip_file(&mut archive, "EGG-INFO/entry_points.txt")?
+            .and_then(|buf| EntryPoints::parse(&buf).ok());
+        // Like `pkg_resources`, the legacy `depends.txt` adds to `requires.txt`,
+        // a file with a malformed section header is ignored like `entry_points.txt`
+        let mut egg_requirements = None;
+        for name in ["EGG-INFO/requires.txt", "EGG-INFO/depends.txt"] {
+            if let Some(requirements) = Self::read_optional_zip_file(&mut archive, name)?
+                .and_then(|buf| parse_requires_txt(&buf).ok())
+            {
+                egg_requirements
+                    .get_or_insert_with(Vec::new)
+                    .extend(requirements);
+            }
+        }
+        let python_version = egg_python_version(egg_filename.as_ref());
+        Ok(Self {
+            entry_points,
+            egg_filename,
+            egg_requirements,
+            ..Self::from_parts(DistributionType::Egg, metadata, python_version)
+        })
     }
 
-    fn parse_wheel(path: &Path) -> Result<Metadata, Error> {
-        Self::parse_zip(path, ".dist-info/METADATA")
+    fn parse_wheel<R: Read + Seek>(
+        mut archive: ZipArchive<R>,
+        wheel_filename: Option<WheelFilename>,
+        verify: bool,
+    ) -> Result<Self, Error> {
+        let metadata_file = Self::find_zip_metadata_file(&archive, ".dist-info/METADATA")?;
+        let metadata = Metadata::parse(&Self::read_zip_file(&mut archive, &metadata_file)?)?;
+        // `WHEEL`, `RECORD` and `entry_points.txt` live next to `METADATA` in the `.dist-info` directory
+        let dist_info_dir = metadata_file
+            .strip_suffix("METADATA")
+            .ok_or(Error::MetadataNotFound)?;
+        // Malformed `WHEEL`, `RECORD` and `entry_points.txt` files don't prevent reading the metadata
+        let wheel_info =
+            Self::read_optional_zip_file(&mut archive, &format!("{dist_info_dir}WHEEL"))?
+                .and_then(|buf| WheelInfo::parse(&buf).ok());
+        let record = Self::read_optional_zip_file(&mut archive, &format!("{dist_info_dir}RECORD"))?;
+        let (record, record_verification) = if verify {
+            let record = Record::parse(&record.ok_or(Error::RecordNotFound)?)?;
+            let record_verification = record.verify_archive(&mut archive)?;
+            (Some(record), Some(record_verification))
+        } else {
+            (record.and_then(|buf| Record::parse(&buf).ok()), None)
+        };
+        let entry_points = Self::read_optional_zip_file(
+            &mut archive,
+            &format!("{dist_info_dir}entry_points.txt"),
+        )?
+        .and_then(|buf| EntryPoints::parse(&buf).ok());
+        let python_version = match (&wheel_filename, &wheel_info) {
+            (Some(wheel_filename), _) => wheel_filename.python_tags.join("."),
+            (None, Some(wheel_info)) if !wheel_info.tags.is_empty() => {
+                wheel_info.python_tags().join(".")
+            }
+            _ => "any".to_string(),
+        };
+        Ok(Self {
+            wheel_filename,
+            wheel_info,
+            record,
+            record_verification,
+            entry_points,
+            ..Self::from_parts(DistributionType::Wheel, metadata, python_version)
+        })
+    }
+
+    /// Guess the distribution type of a zip archive from its contents
+    fn detect_zip_type<R: Read + Seek>(archive: &ZipArchive<R>) -> DistributionType {
+        let is_top_level = |name: &str| Path::new(name).components().count() == 2;
+        if archive
+            .file_names()
+            .any(|name| name.ends_with(".dist-info/WHEEL") && is_top_level(name))
+        {
+            DistributionType::Wheel
+        } else if archive.file_names().any(|name| name == "EGG-INFO/PKG-INFO") {
+            DistributionType::Egg
+        } else {
+            DistributionType::SDist
+        }
     }
 
     fn parse_tar<R: Read>(reader: R) -> Result<Metadata, Error> {
         let mut reader = tar::Archive::new(reader);
-        let metadata_file = reader
-            .entries()?
-            .map(|entry| -> Result<_, Error> {
-                let entry = entry?;
-                if entry.path()?.ends_with("PKG-INFO") {
-                    Ok(Some(entry))
-                } else {
-                    Ok(None)
-                }
-            })
-            .find_map(|x| x.transpose());
-        if let Some(metadata_file) = metadata_file {
-            let mut entry = metadata_file?;
-            let mut buf = Vec::new();
-            entry.read_to_end(&mut buf)?;
-            Metadata::parse(&buf)
-        } else {
-            Err(Error::MetadataNotFound)
+        // Tar archives can't be rewound, so keep every candidate until all entries are seen
+        let mut metadata_files = Vec::new();
+        for entry in reader.entries()? {
+            let mut entry = entry?;
+            let path = entry.path()?;
+            if path.ends_with("PKG-INFO") {
+                let name = path.to_string_lossy().into_owned();
+                let mut buf = Vec::new();
+                entry.read_to_end(&mut buf)?;
+                metadata_files.push((name, buf));
+            }
         }
+        let names = metadata_files
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect();
+        let metadata_file = select_metadata_file(names, 2)?;
+        let (_, buf) = metadata_files
+            .iter()
+            .find(|(name, _)| *name == metadata_file)
+            .ok_or(Error::MetadataNotFound)?;
+        Metadata::parse(buf)
     }
 
-    fn parse_zip(path: &Path, metadata_file_suffix: &str) -> Result<Metadata, Error> {
-        let reader = BufReader::new(fs_err::File::open(path)?);
-        let mut archive = ZipArchive::new(reader)?;
+    fn parse_zip<R: Read + Seek>(
+        mut archive: ZipArchive<R>,
+        metadata_file_suffix: &str,
+    ) -> Result<Metadata, Error> {
+        let metadata_file = Self::find_zip_metadata_file(&archive, metadata_file_suffix)?;
+        Metadata::parse(&Self::read_zip_file(&mut archive, &metadata_file)?)
+    }
+
+    fn find_zip_metadata_file<R: Read + Seek>(
+        archive: &ZipArchive<R>,
+        metadata_file_suffix: &str,
+    ) -> Result<String, Error> {
         let metadata_files: Vec<_> = archive
             .file_names()
             .filter(|name| name.ends_with(metadata_file_suffix))
             .map(ToString::to_string)
             .collect();
-        match metadata_files.as_slice() {
-            [] => Err(Error::MetadataNotFound),
-            [metadata_file] => {
-                let mut buf = Vec::new();
-                archive.by_name(metadata_file)?.read_to_end(&mut buf)?;
-                Metadata::parse(&buf)
-            }
-            [file1, file2]
-                if file1.ends_with(".egg-info/PKG-INFO")
-                    || file2.ends_with(".egg-info/PKG-INFO") =>
-            {
-                let mut buf = Vec::new();
-                archive.by_name(file1)?.read_to_end(&mut buf)?;
-                Metadata::parse(&buf)
-            }
-            _ => {
-                let top_level_files: Vec<_> = metadata_files
-                    .iter()
-                    .filter(|f| {
-                        let path = Path::new(f);
-                        path.components().count() == 2
-                    })
-                    .collect();
-                if top_level_files.len() == 1 {
-                    let mut buf = Vec::new();
-                    archive.by_name(top_level_files[0])?.read_to_end(&mut buf)?;
-                    return Metadata::parse(&buf);
-                }
-                Err(Error::MultipleMetadataFiles(metadata_files))
-            }
+        select_metadata_file(metadata_files, 2)
+    }
+
+    fn read_zip_file<R: Read + Seek>(
+        archive: &mut ZipArchive<R>,
+        name: &str,
+    ) -> Result<Vec<u8>, Error> {
+        let mut buf = Vec::new();
+        archive.by_name(name)?.read_to_end(&mut buf)?;
+        Ok(buf)
+    }
+
+    fn read_optional_zip_file<R: Read + Seek>(
+        archive: &mut ZipArchive<R>,
+        name: &str,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        match Self::read_zip_file(archive, name) {
+            Ok(buf) => Ok(Some(buf)),
+            Err(Error::Zip(ZipError::FileNotFound)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{python_tag_supports, select_metadata_file};
+    use crate::Error;
+
+    #[test]
+    fn test_select_metadata_file() {
+        let select = |files: &[&str]| {
+            select_metadata_file(files.iter().map(ToString::to_string).collect(), 2)
+        };
+        assert_eq!(select(&["foo-1.0/PKG-INFO"]).unwrap(), "foo-1.0/PKG-INFO");
+        for files in [
+            ["foo-1.0/src/foo.egg-info/PKG-INFO", "foo-1.0/PKG-INFO"],
+            ["foo-1.0/PKG-INFO", "foo-1.0/src/foo.egg-info/PKG-INFO"],
+            ["foo-1.0/vendor/bar/PKG-INFO", "foo-1.0/PKG-INFO"],
+        ] {
+            assert_eq!(select(&files).unwrap(), "foo-1.0/PKG-INFO");
+        }
+        let files = [
+            "./foo-1.0/src/foo.egg-info/PKG-INFO",
+            "./foo-1.0/PKG-INFO",
+            "./foo-1.0/vendor/bar/PKG-INFO",
+        ];
+        assert_eq!(select(&files).unwrap(), "./foo-1.0/PKG-INFO");
+        for files in [
+            [
+                "foo-1.0/vendor/bar/PKG-INFO",
+                "foo-1.0/src/foo.egg-info/PKG-INFO",
+            ],
+            [
+                "foo-1.0/src/foo.egg-info/PKG-INFO",
+                "foo-1.0/vendor/bar/PKG-INFO",
+            ],
+        ] {
+            assert_eq!(select(&files).unwrap(), "foo-1.0/src/foo.egg-info/PKG-INFO");
+        }
+        assert!(matches!(select(&[]), Err(Error::MetadataNotFound)));
+        assert!(matches!(
+            select(&["foo-1.0/a/PKG-INFO", "foo-1.0/b/PKG-INFO"]),
+            Err(Error::MultipleMetadataFiles(_))
+        ));
+    }
+
+    #[test]
+    fn test_python_tag_supports() {
+        for (python_tag, abi_tag, python_version, expected) in [
+            ("py3", "none", (3, 12), true),
+            ("py3", "none", (2, 7), false),
+            ("py2", "none", (3, 12), false),
+            ("py38", "none", (3, 12), true),
+            ("py38", "none", (3, 7), false),
+            ("cp38", "abi3", (3, 12), true),
+            ("cp38", "abi3", (3, 7), false),
+            ("cp38", "cp38", (3, 8), true),
+            ("cp38", "cp38", (3, 9), false),
+            ("cp310", "cp310", (3, 10), true),
+            ("pp39", "pypy39_pp73", (3, 9), true),
+            ("foo", "none", (3, 12), true),
+        ] {
+            assert_eq!(
+                python_tag_supports(python_tag, abi_tag, python_version),
+                expected,
+                "{python_tag}-{abi_tag} on {python_version:?}"
+            );
         }
     }
 }
@@ -0,0 +1,207 @@
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, MarkerTree, PackageName, Requirement};
+
+/// A parsed egg filename
+///
+/// `{name}-{version}(-py{python version}(-{platform})?)?.egg`, where the platform may
+/// itself contain `-`, e.g. `foo-1.0-py3.9-linux-x86_64.egg`.
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EggFilename {
+    /// The distribution name as written in the filename, compared after PEP 503 normalization.
+    pub name: PackageName,
+    /// The distribution version.
+    pub version: String,
+    /// The Python version without the `py` prefix, e.g. `3.9`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pyver: Option<String>,
+    /// The platform of eggs with compiled extensions, e.g. `linux-x86_64`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub platform: Option<String>,
+}
+
+impl fmt::Display for EggFilename {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.name, self.version)?;
+        if let Some(pyver) = &self.pyver {
+            write!(f, "-py{pyver}")?;
+            if let Some(platform) = &self.platform {
+                write!(f, "-{platform}")?;
+            }
+        }
+        write!(f, ".egg")
+    }
+}
+
+impl FromStr for EggFilename {
+    type Err = Error;
+
+    fn from_str(filename: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| Error::InvalidEggFilename {
+            filename: filename.to_string(),
+            reason: reason.to_string(),
+        };
+        let stem = filename
+            .strip_suffix(".egg")
+            .ok_or_else(|| invalid("must end with .egg"))?;
+        let parts: Vec<&str> = stem.splitn(4, '-').collect();
+        if parts.iter().any(|part| part.is_empty()) {
+            return Err(invalid("must not contain empty components"));
+        }
+        let (name, version, pyver, platform) = match parts.as_slice() {
+            [name, version] => (name, version, None, None),
+            [name, version, pyver] => (name, version, Some(pyver), None),
+            [name, version, pyver, platform] => (name, version, Some(pyver), Some(platform)),
+            _ => return Err(invalid("expected a name and a version")),
+        };
+        let pyver = pyver
+            .map(|pyver| {
+                pyver
+                    .strip_prefix("py")
+                    .filter(|pyver| !pyver.is_empty())
+                    .ok_or_else(|| invalid("Python version must start with py"))
+            })
+            .transpose()?;
+        let name = PackageName::new(*name).map_err(|_| invalid("invalid distribution name"))?;
+        Ok(Self {
+            name,
+            version: version.to_string(),
+            pyver: pyver.map(ToString::to_string),
+            platform: platform.map(ToString::to_string),
+        })
+    }
+}
+
+/// Parse the `requires.txt` or `depends.txt` file of an egg into PEP 508 requirements
+///
+/// Requirements in a `[extra]`, `[extra:marker]` or `[:marker]` section get the
+/// extra and the marker added to their own marker, like `pkg_resources` does.
+/// Lines that aren't valid PEP 508 requirements, such as `foo==dev` from old eggs,
+/// are skipped, only a malformed section header is an error.
+pub(crate) fn parse_requires_txt(content: &[u8]) -> Result<Vec<Requirement>, Error> {
+    let content = String::from_utf8_lossy(content);
+    let mut requirements = Vec::new();
+    let mut section_marker: Option<MarkerTree> = None;
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = |reason: String| Error::InvalidRequiresTxt {
+            line: index + 1,
+            reason,
+        };
+        if let Some(section) = line.strip_prefix('[') {
+            let section = section
+                .strip_suffix(']')
+                .ok_or_else(|| invalid("unterminated section header".to_string()))?;
+            let (extra, marker) = section.split_once(':').unwrap_or((section, ""));
+            let marker = match marker.trim() {
+                "" => None,
+                marker => Some(
+                    marker
+                        .parse::<MarkerTree>()
+                        .map_err(|err| invalid(err.to_string()))?,
+                ),
+            };
+            section_marker = match extra.trim() {
+                "" => marker,
+                extra => Some(match marker {
+                    Some(marker) => marker.and(MarkerTree::extra(extra)),
+                    None => MarkerTree::extra(extra),
+                }),
+            };
+            continue;
+        }
+        let Ok(mut requirement) = line.parse::<Requirement>() else {
+            continue;
+        };
+        if let Some(marker) = &section_marker {
+            requirement.add_marker(marker.clone());
+        }
+        requirements.push(requirement);
+    }
+    Ok(requirements)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_requires_txt, EggFilename};
+    use crate::Error;
+
+    #[test]
+    fn test_parse_egg_filename() {
+        let filename: EggFilename = "build-0.4.0-py3.9.egg".parse().unwrap();
+        assert_eq!(filename.name, "build");
+        assert_eq!(filename.version, "0.4.0");
+        assert_eq!(filename.pyver.as_deref(), Some("3.9"));
+        assert_eq!(filename.platform, None);
+        assert_eq!(filename.to_string(), "build-0.4.0-py3.9.egg");
+
+        let filename: EggFilename = "Foo_Bar-1.0-py2.7-macosx-10.9-x86_64.egg".parse().unwrap();
+        assert_eq!(filename.name, "foo-bar");
+        assert_eq!(filename.pyver.as_deref(), Some("2.7"));
+        assert_eq!(filename.platform.as_deref(), Some("macosx-10.9-x86_64"));
+        assert_eq!(
+            filename.to_string(),
+            "Foo_Bar-1.0-py2.7-macosx-10.9-x86_64.egg"
+        );
+
+        let filename: EggFilename = "foo-1.0.egg".parse().unwrap();
+        assert_eq!(filename.pyver, None);
+        assert_eq!(filename.to_string(), "foo-1.0.egg");
+
+        for filename in [
+            "foo-1.0-py3.9.zip",
+            "foo.egg",
+            "foo--py3.9.egg",
+            "foo-1.0-3.9.egg",
+            "foo-1.0-py.egg",
+            "foo!-1.0-py3.9.egg",
+        ] {
+            let result: Result<EggFilename, Error> = filename.parse();
+            assert!(
+                matches!(result, Err(Error::InvalidEggFilename { .. })),
+                "{filename}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_requires_txt() {
+        let content = b"six\nrequests>=2.0\n\n[:sys_platform == \"win32\"]\ncolorama\n\n[socks]\nPySocks!=1.5.7\n\n[test:python_version < \"3\"]\nmock; os_name == \"nt\"\n";
+        let requirements: Vec<String> = parse_requires_txt(content)
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            requirements,
+            [
+                "six",
+                "requests>=2.0",
+                "colorama; sys_platform == \"win32\"",
+                "PySocks!=1.5.7; extra == \"socks\"",
+                "mock; os_name == \"nt\" and python_version < \"3\" and extra == \"test\"",
+            ]
+        );
+
+        let result = parse_requires_txt(b"six\n[test\nmock");
+        assert!(matches!(
+            result,
+            Err(Error::InvalidRequiresTxt { line: 2, .. })
+        ));
+        let requirements: Vec<String> =
+            parse_requires_txt(b"six\nfoo==dev\n[test]\nmock >= !\npytest")
+                .unwrap()
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+        assert_eq!(requirements, ["six", "pytest; extra == \"test\""]);
+    }
+}
@@ -0,0 +1,241 @@
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Error;
+
+/// A single entry point, e.g. `pyproject-build = build.__main__:entrypoint` in `[console_scripts]`
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EntryPoint {
+    /// The group the entry point belongs to, e.g. `console_scripts`.
+    pub group: String,
+    /// The name of the entry point within its group.
+    pub name: String,
+    /// The module to import, usually a dotted path.
+    pub module: String,
+    /// The object within the module, usually a dotted path, if any.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub attr: Option<String>,
+    /// Extras required by the entry point.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub extras: Vec<String>,
+}
+
+impl EntryPoint {
+    /// Returns the object reference in `module:attr [extras]` form
+    pub fn value(&self) -> String {
+        let mut value = self.module.clone();
+        if let Some(attr) = &self.attr {
+            value.push(':');
+            value.push_str(attr);
+        }
+        if !self.extras.is_empty() {
+            value.push_str(&format!(" [{}]", self.extras.join(", ")));
+        }
+        value
+    }
+}
+
+impl fmt::Display for EntryPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = {}", self.name, self.value())
+    }
+}
+
+/// Parsed contents of an `entry_points.txt` file
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EntryPoints {
+    /// All entry points, in order of appearance.
+    pub entries: Vec<EntryPoint>,
+}
+
+impl EntryPoints {
+    /// Parse the contents of an `entry_points.txt` file
+    ///
+    /// Like `importlib.metadata`, object references aren't validated and indented
+    /// continuation lines are appended to the previous value.
+    pub fn parse(content: &[u8]) -> Result<Self, Error> {
+        let content = String::from_utf8_lossy(content);
+        // Group, name and value, object references are split once continuation lines are joined
+        let mut raw_entries: Vec<(String, String, String)> = Vec::new();
+        let mut group = None;
+        let mut can_continue = false;
+        for (index, raw_line) in content.lines().enumerate() {
+            let line = raw_line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            if can_continue && raw_line.starts_with([' ', '\t']) {
+                if let Some((_, _, value)) = raw_entries.last_mut() {
+                    value.push(' ');
+                    value.push_str(line);
+                }
+                continue;
+            }
+            let invalid = |reason: &str| Error::InvalidEntryPoint {
+                line: index + 1,
+                reason: reason.to_string(),
+            };
+            if let Some(section) = line.strip_prefix('[') {
+                let section = section
+                    .strip_suffix(']')
+                    .ok_or_else(|| invalid("unterminated group header"))?
+                    .trim();
+                group = Some(section.to_string());
+                can_continue = false;
+                continue;
+            }
+            let group = group
+                .as_ref()
+                .ok_or_else(|| invalid("entry point outside of a group"))?;
+            let (name, value) = line
+                .split_once('=')
+                .ok_or_else(|| invalid("expected `name = value`"))?;
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(invalid("empty entry point name"));
+            }
+            raw_entries.push((group.clone(), name.to_string(), value.trim().to_string()));
+            can_continue = true;
+        }
+        let entries = raw_entries
+            .into_iter()
+            .map(|(group, name, value)| {
+                let (module, attr, extras) = parse_object_reference(&value);
+                EntryPoint {
+                    group,
+                    name,
+                    module,
+                    attr,
+                    extras,
+                }
+            })
+            .collect();
+        Ok(Self { entries })
+    }
+
+    /// Returns the entry points of `group`
+    pub fn group<'a>(&'a self, group: &'a str) -> impl Iterator<Item = &'a EntryPoint> + 'a {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.group == group)
+    }
+
+    /// Returns the distinct group names, in order of appearance
+    pub fn groups(&self) -> Vec<&str> {
+        let mut groups = Vec::new();
+        for entry in &self.entries {
+            if !groups.contains(&entry.group.as_str()) {
+                groups.push(entry.group.as_str());
+            }
+        }
+        groups
+    }
+}
+
+impl FromStr for EntryPoints {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        EntryPoints::parse(s.as_bytes())
+    }
+}
+
+/// Split `module.path:attr.path [extra1, extra2]`, without validating the names
+fn parse_object_reference(value: &str) -> (String, Option<String>, Vec<String>) {
+    let (reference, extras) = match value.split_once('[') {
+        Some((reference, extras)) => {
+            let extras = extras.trim_end();
+            let extras = extras
+                .strip_suffix(']')
+                .unwrap_or(extras)
+                .split(',')
+                .map(str::trim)
+                .filter(|extra| !extra.is_empty())
+                .map(ToString::to_string)
+                .collect();
+            (reference, extras)
+        }
+        None => (value, Vec::new()),
+    };
+    let (module, attr) = match reference.split_once(':') {
+        Some((module, attr)) => (module, Some(attr.trim()).filter(|attr| !attr.is_empty())),
+        None => (reference, None),
+    };
+    (
+        module.trim().to_string(),
+        attr.map(ToString::to_string),
+        extras,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EntryPoint, EntryPoints};
+    use crate::Error;
+
+    #[test]
+    fn test_parse_entry_points() {
+        let s = "[console_scripts]\npyproject-build = build.__main__:entrypoint\n\n# comment\n[pipx.run]\nbuild=build.__main__:entrypoint [virtualenv, uv]\nplugin = build.plugin\n";
+        let entry_points: EntryPoints = s.parse().unwrap();
+        assert_eq!(entry_points.groups(), ["console_scripts", "pipx.run"]);
+        assert_eq!(
+            entry_points.entries[0],
+            EntryPoint {
+                group: "console_scripts".to_string(),
+                name: "pyproject-build".to_string(),
+                module: "build.__main__".to_string(),
+                attr: Some("entrypoint".to_string()),
+                extras: Vec::new(),
+            }
+        );
+        let pipx: Vec<_> = entry_points.group("pipx.run").collect();
+        assert_eq!(pipx.len(), 2);
+        assert_eq!(pipx[0].extras, ["virtualenv", "uv"]);
+        assert_eq!(
+            pipx[0].to_string(),
+            "build = build.__main__:entrypoint [virtualenv, uv]"
+        );
+        assert_eq!(pipx[1].module, "build.plugin");
+        assert_eq!(pipx[1].attr, None);
+    }
+
+    #[test]
+    fn test_parse_lenient_entry_points() {
+        let s = "[console_scripts]\nfoo =\n    foo.cli:main\n    [cli]\nfoo-gui = foo-gui.app:Main.run\nbar = bar:\n";
+        let entry_points: EntryPoints = s.parse().unwrap();
+        let values: Vec<_> = entry_points
+            .entries
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            values,
+            [
+                "foo = foo.cli:main [cli]",
+                "foo-gui = foo-gui.app:Main.run",
+                "bar = bar"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_entry_points() {
+        for s in [
+            "foo = bar:baz",
+            "[console_scripts\nfoo = bar:baz",
+            "[console_scripts]\nfoo",
+            "[console_scripts]\n= bar:baz",
+        ] {
+            let result: Result<EntryPoints, Error> = s.parse();
+            assert!(
+                matches!(result, Err(Error::InvalidEntryPoint { .. })),
+                "{s}"
+            );
+        }
+    }
+}
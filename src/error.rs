@@ -4,6 +4,8 @@ use mailparse::MailParseError;
 use thiserror::Error;
 use zip::result::ZipError;
 
+use crate::{Diagnostic, ParseError};
+
 /// The error type
 #[derive(Error, Debug)]
 pub enum Error {
@@ -16,6 +18,9 @@ pub enum Error {
     /// Zip parse error
     #[error(transparent)]
     Zip(#[from] ZipError),
+    /// TOML parse error
+    #[error(transparent)]
+    Toml(#[from] toml::de::Error),
     /// Metadata field not found
     #[error("metadata field {0} not found")]
     FieldNotFound(&'static str),
@@ -25,7 +30,105 @@ pub enum Error {
     /// Metadata file not found
     #[error("metadata file not found")]
     MetadataNotFound,
+    /// A `Metadata-Version` with a newer major version, rejected by `Metadata::parse_strict`
+    #[error("unsupported metadata version {0}")]
+    UnsupportedMetadataVersion(String),
+    /// The `RECORD` file of a wheel to verify is missing
+    #[error("RECORD file not found")]
+    RecordNotFound,
+    /// Invalid wheel filename
+    #[error("invalid wheel filename {filename:?}: {reason}")]
+    InvalidWheelFilename {
+        /// The offending filename
+        filename: String,
+        /// Why the filename is invalid
+        reason: String,
+    },
+    /// Invalid egg filename
+    #[error("invalid egg filename {filename:?}: {reason}")]
+    InvalidEggFilename {
+        /// The offending filename
+        filename: String,
+        /// Why the filename is invalid
+        reason: String,
+    },
+    /// A source distribution filename that isn't `{name}-{version}.{extension}`
+    #[error("invalid filename {filename:?}: {reason}")]
+    InvalidFilename {
+        /// The offending filename
+        filename: String,
+        /// Why the filename is invalid
+        reason: String,
+    },
+    /// The name or version in the filename doesn't match the metadata
+    #[error("{field} {filename_value:?} in filename {filename:?} doesn't match {metadata_value:?} in metadata")]
+    FilenameMismatch {
+        /// The filename of the distribution
+        filename: String,
+        /// The mismatched field, `name` or `version`
+        field: &'static str,
+        /// The value in the filename
+        filename_value: String,
+        /// The value in the metadata
+        metadata_value: String,
+    },
+    /// Malformed hash or unknown hash algorithm
+    #[error("invalid hash {0:?}, expected `sha256=<hex digest>`")]
+    InvalidHash(String),
+    /// A valid `hashlib` algorithm that can't be verified, e.g. `md5` or `blake2b`
+    #[error("unsupported hash algorithm {0:?}, expected sha256, sha384 or sha512")]
+    UnsupportedHashAlgorithm(String),
+    /// Invalid `RECORD` file
+    #[error("invalid RECORD file at line {line}: {reason}")]
+    InvalidRecord {
+        /// The 1-based line number of the offending row
+        line: usize,
+        /// Why the row is invalid
+        reason: String,
+    },
+    /// Invalid `entry_points.txt` file
+    #[error("invalid entry_points.txt file at line {line}: {reason}")]
+    InvalidEntryPoint {
+        /// The 1-based line number of the offending line
+        line: usize,
+        /// Why the line is invalid
+        reason: String,
+    },
+    /// Invalid `requires.txt` or `depends.txt` file of an egg
+    #[error("invalid requires.txt file at line {line}: {reason}")]
+    InvalidRequiresTxt {
+        /// The 1-based line number of the offending line
+        line: usize,
+        /// Why the line is invalid
+        reason: String,
+    },
+    /// Invalid PEP 440 version
+    #[error("invalid version {version:?}: {reason}")]
+    InvalidVersion {
+        /// The offending version string
+        version: String,
+        /// Why the version is invalid
+        reason: String,
+    },
+    /// Invalid `[project]` table in `pyproject.toml`
+    #[error("invalid pyproject.toml: {0}")]
+    InvalidPyproject(String),
+    /// Invalid distribution name
+    #[error("invalid package name: {0:?}")]
+    InvalidPackageName(String),
+    /// Invalid PEP 508 requirement
+    #[error("invalid requirement: {0}")]
+    InvalidRequirement(ParseError),
+    /// Invalid PEP 508 environment marker
+    #[error("invalid marker: {0}")]
+    InvalidMarker(ParseError),
+    /// Invalid PEP 440 version specifiers
+    #[error("invalid version specifiers: {0}")]
+    InvalidSpecifier(ParseError),
     /// Multiple metadata files found
     #[error("found multiple metadata files: {0:?}")]
     MultipleMetadataFiles(Vec<String>),
+    /// Metadata that doesn't comply with the core metadata specification
+    #[error("invalid metadata: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+    InvalidMetadata(Vec<Diagnostic>),
 }
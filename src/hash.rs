@@ -0,0 +1,73 @@
+use std::io;
+
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+/// Incremental hasher for the algorithms allowed in wheel `RECORD` files
+pub(crate) enum Hasher {
+    Sha256(Sha256),
+    Sha384(Sha384),
+    Sha512(Sha512),
+}
+
+impl Hasher {
+    /// Create a hasher from a `hashlib` algorithm name, e.g. `sha256`
+    ///
+    /// Returns `None` for unknown or insecure algorithms like `md5` and `sha1`.
+    pub(crate) fn new(algorithm: &str) -> Option<Self> {
+        match algorithm {
+            "sha256" => Some(Hasher::Sha256(Sha256::new())),
+            "sha384" => Some(Hasher::Sha384(Sha384::new())),
+            "sha512" => Some(Hasher::Sha512(Sha512::new())),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for the algorithms every `hashlib` provides, including those
+    /// [`Hasher::new`] doesn't support
+    pub(crate) fn is_hashlib_algorithm(algorithm: &str) -> bool {
+        matches!(
+            algorithm,
+            "md5"
+                | "sha1"
+                | "sha224"
+                | "sha256"
+                | "sha384"
+                | "sha512"
+                | "sha3_224"
+                | "sha3_256"
+                | "sha3_384"
+                | "sha3_512"
+                | "blake2b"
+                | "blake2s"
+                | "shake_128"
+                | "shake_256"
+        )
+    }
+
+    pub(crate) fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Sha256(hasher) => hasher.update(data),
+            Hasher::Sha384(hasher) => hasher.update(data),
+            Hasher::Sha512(hasher) => hasher.update(data),
+        }
+    }
+
+    pub(crate) fn finalize(self) -> Vec<u8> {
+        match self {
+            Hasher::Sha256(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha384(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha512(hasher) => hasher.finalize().to_vec(),
+        }
+    }
+}
+
+impl io::Write for Hasher {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
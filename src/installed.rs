@@ -0,0 +1,277 @@
+use std::collections::HashSet;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::egg::parse_requires_txt;
+use crate::{EntryPoints, Error, Metadata, Record, Requirement, WheelInfo};
+
+/// Layout of an installed distribution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstalledType {
+    /// A `.dist-info` directory, written by installers of wheels
+    DistInfo,
+    /// A `.egg-info` directory, written by setuptools
+    EggInfo,
+    /// A bare metadata file, e.g. a `PKG-INFO` or a distutils `.egg-info` file
+    MetadataFile,
+}
+
+/// A distribution installed in a `site-packages` directory
+#[derive(Debug, Clone)]
+pub struct InstalledDistribution {
+    installed_type: InstalledType,
+    path: PathBuf,
+    metadata: Metadata,
+    wheel_info: Option<WheelInfo>,
+    record: Option<Record>,
+    entry_points: Option<EntryPoints>,
+    egg_requirements: Option<Vec<Requirement>>,
+    installer: Option<String>,
+    direct_url: Option<String>,
+    requested: bool,
+}
+
+impl InstalledDistribution {
+    /// Open an installed distribution from a `.dist-info` directory, a `.egg-info`
+    /// directory or a bare metadata file
+    pub fn new(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        if !path.is_dir() {
+            let metadata = Metadata::parse(&fs_err::read(path)?)?;
+            return Ok(Self::from_parts(
+                InstalledType::MetadataFile,
+                path,
+                metadata,
+            ));
+        }
+        // `EGG-INFO` is the metadata directory of an unpacked `.egg`
+        if path.extension().is_some_and(|ext| ext == "egg-info")
+            || path.file_name().is_some_and(|name| name == "EGG-INFO")
+        {
+            let metadata = read_optional(&path.join("PKG-INFO"))?.ok_or(Error::MetadataNotFound)?;
+            let entry_points = read_optional(&path.join("entry_points.txt"))?
+                .and_then(|buf| EntryPoints::parse(&buf).ok());
+            // A `requires.txt` with a malformed section header is ignored like `entry_points.txt`
+            let mut egg_requirements = None;
+            for name in ["requires.txt", "depends.txt"] {
+                if let Some(requirements) =
+                    read_optional(&path.join(name))?.and_then(|buf| parse_requires_txt(&buf).ok())
+                {
+                    egg_requirements
+                        .get_or_insert_with(Vec::new)
+                        .extend(requirements);
+                }
+            }
+            return Ok(Self {
+                entry_points,
+                egg_requirements,
+                ..Self::from_parts(InstalledType::EggInfo, path, Metadata::parse(&metadata)?)
+            });
+        }
+
+        let metadata = read_optional(&path.join("METADATA"))?.ok_or(Error::MetadataNotFound)?;
+        // Malformed `WHEEL`, `RECORD` and `entry_points.txt` files don't prevent reading the metadata
+        let wheel_info =
+            read_optional(&path.join("WHEEL"))?.and_then(|buf| WheelInfo::parse(&buf).ok());
+        let record = read_optional(&path.join("RECORD"))?.and_then(|buf| Record::parse(&buf).ok());
+        let entry_points = read_optional(&path.join("entry_points.txt"))?
+            .and_then(|buf| EntryPoints::parse(&buf).ok());
+        let installer = read_optional(&path.join("INSTALLER"))?
+            .map(|buf| String::from_utf8_lossy(&buf).trim().to_string());
+        let direct_url = read_optional(&path.join("direct_url.json"))?
+            .map(|buf| String::from_utf8_lossy(&buf).into_owned());
+        Ok(Self {
+            wheel_info,
+            record,
+            entry_points,
+            installer,
+            direct_url,
+            requested: path.join("REQUESTED").is_file(),
+            ..Self::from_parts(InstalledType::DistInfo, path, Metadata::parse(&metadata)?)
+        })
+    }
+
+    /// Find all distributions installed in `paths`, like `importlib.metadata.distributions()`
+    ///
+    /// `paths` are searched in `sys.path` order for `.dist-info` and `.egg-info` entries
+    /// and unpacked `.egg` directories. Paths listed in `.pth` files are searched after
+    /// all of `paths` like `site` adds them to the end of `sys.path`, and `.egg-link`
+    /// files of develop installs are followed in place. Every installed copy of a
+    /// distribution is returned, the first one shadows the later ones on import.
+    /// Like `importlib.metadata`, a broken distribution doesn't hide the others: missing or
+    /// unreadable paths and metadata directories that fail to open are skipped.
+    pub fn discover<P: AsRef<Path>>(paths: impl IntoIterator<Item = P>) -> Vec<Self> {
+        let mut search_paths: Vec<PathBuf> = paths
+            .into_iter()
+            .map(|p| p.as_ref().to_path_buf())
+            .collect();
+        let site_dirs = search_paths.len();
+        let mut visited = HashSet::new();
+        let mut distributions = Vec::new();
+        let mut index = 0;
+        while index < search_paths.len() {
+            let search_path = search_paths[index].clone();
+            // Like `site`, only `.pth` files in site directories are processed
+            let is_site_dir = index < site_dirs;
+            index += 1;
+            if !search_path.is_dir() || !visited.insert(canonicalize(&search_path)) {
+                continue;
+            }
+            for entry in read_dir_sorted(&search_path) {
+                let Some(ext) = entry.extension().and_then(|ext| ext.to_str()) else {
+                    continue;
+                };
+                match ext {
+                    "dist-info" | "egg-info" => {
+                        distributions.extend(Self::open_discovered(&entry, &mut visited));
+                    }
+                    "egg" if entry.join("EGG-INFO").is_dir() => {
+                        distributions
+                            .extend(Self::open_discovered(&entry.join("EGG-INFO"), &mut visited));
+                    }
+                    "egg-link" => {
+                        let Ok(content) = fs_err::read_to_string(&entry) else {
+                            continue;
+                        };
+                        let Some(project) = content.lines().next().map(str::trim) else {
+                            continue;
+                        };
+                        let project = search_path.join(project);
+                        for egg_info in read_dir_sorted(&project) {
+                            if egg_info.extension().is_some_and(|ext| ext == "egg-info") {
+                                distributions
+                                    .extend(Self::open_discovered(&egg_info, &mut visited));
+                            }
+                        }
+                    }
+                    "pth" if is_site_dir => {
+                        let Ok(content) = fs_err::read_to_string(&entry) else {
+                            continue;
+                        };
+                        for line in content.lines() {
+                            let line = line.trim_end();
+                            // Lines starting with `import` are executed by `site`, not paths
+                            if line.is_empty()
+                                || line.starts_with('#')
+                                || line.starts_with("import ")
+                                || line.starts_with("import\t")
+                            {
+                                continue;
+                            }
+                            search_paths.push(search_path.join(line));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        distributions
+    }
+
+    /// Open a discovered metadata directory once, skipping it if it fails to open
+    fn open_discovered(path: &Path, visited: &mut HashSet<PathBuf>) -> Option<Self> {
+        if !visited.insert(canonicalize(path)) {
+            return None;
+        }
+        Self::new(path).ok()
+    }
+
+    fn from_parts(installed_type: InstalledType, path: &Path, metadata: Metadata) -> Self {
+        Self {
+            installed_type,
+            path: path.to_path_buf(),
+            metadata,
+            wheel_info: None,
+            record: None,
+            entry_points: None,
+            egg_requirements: None,
+            installer: None,
+            direct_url: None,
+            requested: false,
+        }
+    }
+
+    /// Returns the layout of the installed distribution
+    pub fn r#type(&self) -> InstalledType {
+        self.installed_type
+    }
+
+    /// Returns the path of the metadata directory or file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns distribution metadata
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Returns the parsed `WHEEL` file of a `.dist-info` directory, `None` if it's missing
+    /// or malformed
+    pub fn wheel_info(&self) -> Option<&WheelInfo> {
+        self.wheel_info.as_ref()
+    }
+
+    /// Returns the parsed `RECORD` file of a `.dist-info` directory, `None` if it's missing
+    /// or malformed
+    ///
+    /// Paths are relative to the directory containing the `.dist-info` directory.
+    pub fn record(&self) -> Option<&Record> {
+        self.record.as_ref()
+    }
+
+    /// Returns the entry points declared in `entry_points.txt`, `None` if it's missing or
+    /// malformed
+    pub fn entry_points(&self) -> Option<&EntryPoints> {
+        self.entry_points.as_ref()
+    }
+
+    /// Returns the requirements declared in `requires.txt` and `depends.txt` of a
+    /// `.egg-info` directory
+    pub fn egg_requirements(&self) -> Option<&[Requirement]> {
+        self.egg_requirements.as_deref()
+    }
+
+    /// Returns the name of the tool that installed the distribution from the `INSTALLER` file
+    pub fn installer(&self) -> Option<&str> {
+        self.installer.as_deref()
+    }
+
+    /// Returns the raw content of `direct_url.json` for distributions installed from a URL
+    /// or a local directory, see [PEP 610](https://peps.python.org/pep-0610/)
+    pub fn direct_url(&self) -> Option<&str> {
+        self.direct_url.as_deref()
+    }
+
+    /// Returns `true` if the distribution was requested by the user rather than installed
+    /// as a dependency, according to the `REQUESTED` file
+    pub fn requested(&self) -> bool {
+        self.requested
+    }
+}
+
+/// Canonicalize a path to detect paths that were already visited, falling back to the path itself
+fn canonicalize(path: &Path) -> PathBuf {
+    fs_err::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// List the entries of a directory in sorted order, empty if it can't be read
+fn read_dir_sorted(path: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs_err::read_dir(path) else {
+        return Vec::new();
+    };
+    let mut entries: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect();
+    entries.sort();
+    entries
+}
+
+/// Read a file, `None` if it doesn't exist
+fn read_optional(path: &Path) -> Result<Option<Vec<u8>>, Error> {
+    match fs_err::read(path) {
+        Ok(buf) => Ok(Some(buf)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
@@ -1,7 +1,37 @@
 mod distribution;
+mod egg;
+mod entry_points;
 mod error;
+mod hash;
+mod installed;
+mod marker;
 mod metadata;
+mod name;
+mod parser;
+mod project_url;
+mod pyproject;
+mod record;
+mod requirement;
+mod specifier;
+mod validate;
+mod version;
+mod wheel;
 
 pub use crate::distribution::{Distribution, DistributionType};
+pub use crate::egg::EggFilename;
+pub use crate::entry_points::{EntryPoint, EntryPoints};
 pub use crate::error::Error;
-pub use crate::metadata::Metadata;
+pub use crate::installed::{InstalledDistribution, InstalledType};
+pub use crate::marker::{
+    MarkerEnvironment, MarkerExpression, MarkerOperator, MarkerTree, MarkerValue,
+};
+pub use crate::metadata::{Metadata, MetadataVersion, ParseWarning};
+pub use crate::name::PackageName;
+pub use crate::parser::ParseError;
+pub use crate::project_url::ProjectUrl;
+pub use crate::record::{Record, RecordEntry, RecordMismatch, RecordVerification};
+pub use crate::requirement::{Requirement, VersionOrUrl};
+pub use crate::specifier::{Operator, VersionSpecifier, VersionSpecifiers};
+pub use crate::validate::{Diagnostic, Severity};
+pub use crate::version::{LocalSegment, PreRelease, PreReleaseKind, Version};
+pub use crate::wheel::{BuildTag, WheelFilename, WheelInfo};
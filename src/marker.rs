@@ -0,0 +1,552 @@
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::name;
+use crate::parser::{Cursor, ParseError};
+use crate::{Error, Operator, Version, VersionSpecifier};
+
+/// Environment marker variables defined by PEP 508
+const MARKER_VARIABLES: &[&str] = &[
+    "implementation_name",
+    "implementation_version",
+    "os_name",
+    "platform_machine",
+    "platform_python_implementation",
+    "platform_release",
+    "platform_system",
+    "platform_version",
+    "python_full_version",
+    "python_version",
+    "sys_platform",
+    "extra",
+];
+
+/// Legacy spellings accepted by `packaging` and their PEP 508 names
+const LEGACY_MARKER_VARIABLES: &[(&str, &str)] = &[
+    ("os.name", "os_name"),
+    ("sys.platform", "sys_platform"),
+    ("platform.version", "platform_version"),
+    ("platform.machine", "platform_machine"),
+    (
+        "platform.python_implementation",
+        "platform_python_implementation",
+    ),
+    ("python_implementation", "platform_python_implementation"),
+];
+
+/// The values of the environment marker variables of a target interpreter
+///
+/// The values are what the corresponding Python expressions listed in PEP 508
+/// return on the target, e.g. `platform.python_version()[:3]` for `python_version`.
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MarkerEnvironment {
+    /// `sys.implementation.name`, e.g. `cpython`.
+    pub implementation_name: String,
+    /// Version of the implementation, e.g. `3.12.1`.
+    pub implementation_version: String,
+    /// `os.name`, e.g. `posix`.
+    pub os_name: String,
+    /// `platform.machine()`, e.g. `x86_64`.
+    pub platform_machine: String,
+    /// `platform.python_implementation()`, e.g. `CPython`.
+    pub platform_python_implementation: String,
+    /// `platform.release()`, e.g. `6.1.0`.
+    pub platform_release: String,
+    /// `platform.system()`, e.g. `Linux`.
+    pub platform_system: String,
+    /// `platform.version()`.
+    pub platform_version: String,
+    /// `platform.python_version()`, e.g. `3.12.1`.
+    pub python_full_version: String,
+    /// `'.'.join(platform.python_version_tuple()[:2])`, e.g. `3.12`.
+    pub python_version: String,
+    /// `sys.platform`, e.g. `linux`.
+    pub sys_platform: String,
+}
+
+impl MarkerEnvironment {
+    /// Returns the value of a marker variable, `None` for `extra` and unknown names
+    pub fn get(&self, name: &str) -> Option<&str> {
+        let value = match name {
+            "implementation_name" => &self.implementation_name,
+            "implementation_version" => &self.implementation_version,
+            "os_name" => &self.os_name,
+            "platform_machine" => &self.platform_machine,
+            "platform_python_implementation" => &self.platform_python_implementation,
+            "platform_release" => &self.platform_release,
+            "platform_system" => &self.platform_system,
+            "platform_version" => &self.platform_version,
+            "python_full_version" => &self.python_full_version,
+            "python_version" => &self.python_version,
+            "sys_platform" => &self.sys_platform,
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+/// Comparison operator of a marker expression
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MarkerOperator {
+    /// `==`
+    Equal,
+    /// `!=`
+    NotEqual,
+    /// `<`
+    LessThan,
+    /// `<=`
+    LessThanEqual,
+    /// `>`
+    GreaterThan,
+    /// `>=`
+    GreaterThanEqual,
+    /// `~=`
+    TildeEqual,
+    /// `===`
+    ExactEqual,
+    /// `in`
+    In,
+    /// `not in`
+    NotIn,
+}
+
+impl fmt::Display for MarkerOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operator = match self {
+            MarkerOperator::Equal => "==",
+            MarkerOperator::NotEqual => "!=",
+            MarkerOperator::LessThan => "<",
+            MarkerOperator::LessThanEqual => "<=",
+            MarkerOperator::GreaterThan => ">",
+            MarkerOperator::GreaterThanEqual => ">=",
+            MarkerOperator::TildeEqual => "~=",
+            MarkerOperator::ExactEqual => "===",
+            MarkerOperator::In => "in",
+            MarkerOperator::NotIn => "not in",
+        };
+        write!(f, "{operator}")
+    }
+}
+
+/// One side of a marker expression
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MarkerValue {
+    /// An environment variable such as `python_version`, legacy spellings are normalized
+    Variable(String),
+    /// A quoted string literal, without the quotes
+    String(String),
+}
+
+impl fmt::Display for MarkerValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MarkerValue::Variable(name) => write!(f, "{name}"),
+            // Prefer double quotes like `packaging`, unless the value contains one
+            MarkerValue::String(value) if value.contains('"') => write!(f, "'{value}'"),
+            MarkerValue::String(value) => write!(f, "\"{value}\""),
+        }
+    }
+}
+
+/// A single comparison such as `python_version >= "3.8"`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MarkerExpression {
+    /// The left hand side.
+    pub left: MarkerValue,
+    /// The comparison operator.
+    pub operator: MarkerOperator,
+    /// The right hand side.
+    pub right: MarkerValue,
+}
+
+impl MarkerExpression {
+    /// Evaluate the expression with `extra` set to `extra`
+    fn evaluate(&self, env: &MarkerEnvironment, extra: &str) -> bool {
+        let is_extra = [&self.left, &self.right]
+            .iter()
+            .any(|value| matches!(value, MarkerValue::Variable(name) if name == "extra"));
+        let resolve = |value: &MarkerValue| -> String {
+            let value = match value {
+                MarkerValue::Variable(name) if name == "extra" => extra,
+                MarkerValue::Variable(name) => env.get(name).unwrap_or_default(),
+                MarkerValue::String(value) => value,
+            };
+            // Extras are compared by their normalized names
+            if is_extra {
+                name::normalize(value)
+            } else {
+                value.to_string()
+            }
+        };
+        let left = resolve(&self.left);
+        let right = resolve(&self.right);
+        match self.operator {
+            MarkerOperator::In => right.contains(&left),
+            MarkerOperator::NotIn => !right.contains(&left),
+            _ => {
+                // Use PEP 440 semantics if both sides are versions, e.g. `python_version`
+                if let (Ok(version), Some(specifier)) =
+                    (left.parse::<Version>(), self.specifier(&right))
+                {
+                    return specifier.contains(&version);
+                }
+                match self.operator {
+                    MarkerOperator::Equal | MarkerOperator::ExactEqual => left == right,
+                    MarkerOperator::NotEqual => left != right,
+                    MarkerOperator::LessThan => left < right,
+                    MarkerOperator::LessThanEqual => left <= right,
+                    MarkerOperator::GreaterThan => left > right,
+                    MarkerOperator::GreaterThanEqual => left >= right,
+                    MarkerOperator::TildeEqual => false,
+                    MarkerOperator::In | MarkerOperator::NotIn => unreachable!(),
+                }
+            }
+        }
+    }
+
+    /// Build a version specifier from the operator and `version`, if it is a valid one
+    fn specifier(&self, version: &str) -> Option<VersionSpecifier> {
+        let operator = match self.operator {
+            MarkerOperator::Equal => Operator::Equal,
+            MarkerOperator::NotEqual => Operator::NotEqual,
+            MarkerOperator::LessThan => Operator::LessThan,
+            MarkerOperator::LessThanEqual => Operator::LessThanEqual,
+            MarkerOperator::GreaterThan => Operator::GreaterThan,
+            MarkerOperator::GreaterThanEqual => Operator::GreaterThanEqual,
+            MarkerOperator::TildeEqual => Operator::TildeEqual,
+            MarkerOperator::ExactEqual => Operator::ExactEqual,
+            MarkerOperator::In | MarkerOperator::NotIn => return None,
+        };
+        let specifier = format!("{operator}{version}");
+        let mut cursor = Cursor::new(&specifier);
+        let specifier = VersionSpecifier::parse(&mut cursor).ok()?;
+        cursor.at_end().then_some(specifier)
+    }
+}
+
+impl fmt::Display for MarkerExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.left, self.operator, self.right)
+    }
+}
+
+/// A parsed PEP 508 environment marker
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MarkerTree {
+    /// A single comparison
+    Expression(MarkerExpression),
+    /// All of the markers must hold
+    And(Vec<MarkerTree>),
+    /// Any of the markers must hold
+    Or(Vec<MarkerTree>),
+}
+
+impl fmt::Display for MarkerTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MarkerTree::Expression(expression) => write!(f, "{expression}"),
+            MarkerTree::And(markers) => {
+                for (i, marker) in markers.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " and ")?;
+                    }
+                    match marker {
+                        MarkerTree::Or(_) => write!(f, "({marker})")?,
+                        _ => write!(f, "{marker}")?,
+                    }
+                }
+                Ok(())
+            }
+            MarkerTree::Or(markers) => {
+                let markers: Vec<String> = markers.iter().map(ToString::to_string).collect();
+                write!(f, "{}", markers.join(" or "))
+            }
+        }
+    }
+}
+
+impl MarkerTree {
+    /// Evaluate the marker against `env` with the requested `extras`
+    ///
+    /// Like pip, the marker holds if it evaluates to true with `extra` set to
+    /// either an empty string or any of the requested extras.
+    pub fn evaluate(&self, env: &MarkerEnvironment, extras: &[&str]) -> bool {
+        std::iter::once("")
+            .chain(extras.iter().copied())
+            .any(|extra| self.evaluate_extra(env, extra))
+    }
+
+    /// The `extra == "{extra}"` marker of a requirement only needed for an extra,
+    /// with the extra name normalized following PEP 685
+    pub(crate) fn extra(extra: &str) -> Self {
+        MarkerTree::Expression(MarkerExpression {
+            left: MarkerValue::Variable("extra".to_string()),
+            operator: MarkerOperator::Equal,
+            right: MarkerValue::String(name::normalize(extra)),
+        })
+    }
+
+    /// Combine two markers that must both hold
+    pub(crate) fn and(self, other: Self) -> Self {
+        match self {
+            MarkerTree::And(mut markers) => {
+                markers.push(other);
+                MarkerTree::And(markers)
+            }
+            marker => MarkerTree::And(vec![marker, other]),
+        }
+    }
+
+    fn evaluate_extra(&self, env: &MarkerEnvironment, extra: &str) -> bool {
+        match self {
+            MarkerTree::Expression(expression) => expression.evaluate(env, extra),
+            MarkerTree::And(markers) => markers.iter().all(|m| m.evaluate_extra(env, extra)),
+            MarkerTree::Or(markers) => markers.iter().any(|m| m.evaluate_extra(env, extra)),
+        }
+    }
+
+    /// Parse a marker at the cursor
+    pub(crate) fn parse(cursor: &mut Cursor) -> Result<Self, ParseError> {
+        Self::parse_or(cursor)
+    }
+
+    fn parse_or(cursor: &mut Cursor) -> Result<Self, ParseError> {
+        let mut markers = vec![Self::parse_and(cursor)?];
+        loop {
+            cursor.eat_whitespace();
+            if !cursor.eat_keyword("or") {
+                break;
+            }
+            markers.push(Self::parse_and(cursor)?);
+        }
+        Ok(if markers.len() == 1 {
+            markers.remove(0)
+        } else {
+            MarkerTree::Or(markers)
+        })
+    }
+
+    fn parse_and(cursor: &mut Cursor) -> Result<Self, ParseError> {
+        let mut markers = vec![Self::parse_atom(cursor)?];
+        loop {
+            cursor.eat_whitespace();
+            if !cursor.eat_keyword("and") {
+                break;
+            }
+            markers.push(Self::parse_atom(cursor)?);
+        }
+        Ok(if markers.len() == 1 {
+            markers.remove(0)
+        } else {
+            MarkerTree::And(markers)
+        })
+    }
+
+    fn parse_atom(cursor: &mut Cursor) -> Result<Self, ParseError> {
+        cursor.eat_whitespace();
+        if cursor.eat("(") {
+            let marker = Self::parse_or(cursor)?;
+            cursor.eat_whitespace();
+            cursor.expect(')')?;
+            return Ok(marker);
+        }
+        let left = parse_marker_value(cursor)?;
+        cursor.eat_whitespace();
+        let operator = parse_marker_operator(cursor)?;
+        cursor.eat_whitespace();
+        let right = parse_marker_value(cursor)?;
+        Ok(MarkerTree::Expression(MarkerExpression {
+            left,
+            operator,
+            right,
+        }))
+    }
+}
+
+fn parse_marker_value(cursor: &mut Cursor) -> Result<MarkerValue, ParseError> {
+    let start = cursor.pos();
+    match cursor.peek() {
+        Some(quote @ ('\'' | '"')) => {
+            cursor.next();
+            let value = cursor.take_while(|c| c != quote);
+            if cursor.next().is_none() {
+                return Err(cursor.error("unterminated string", start, cursor.pos() - start));
+            }
+            Ok(MarkerValue::String(value.to_string()))
+        }
+        Some(c) if c.is_ascii_alphabetic() => {
+            let name = cursor.take_while(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+            if MARKER_VARIABLES.contains(&name) {
+                Ok(MarkerValue::Variable(name.to_string()))
+            } else if let Some((_, normalized)) = LEGACY_MARKER_VARIABLES
+                .iter()
+                .find(|(legacy, _)| *legacy == name)
+            {
+                Ok(MarkerValue::Variable(normalized.to_string()))
+            } else {
+                Err(cursor.error(
+                    format!("unknown marker variable `{name}`"),
+                    start,
+                    name.len(),
+                ))
+            }
+        }
+        _ => Err(cursor.error_here("expected a marker variable or quoted string")),
+    }
+}
+
+fn parse_marker_operator(cursor: &mut Cursor) -> Result<MarkerOperator, ParseError> {
+    const OPERATORS: &[(&str, MarkerOperator)] = &[
+        ("===", MarkerOperator::ExactEqual),
+        ("==", MarkerOperator::Equal),
+        ("!=", MarkerOperator::NotEqual),
+        ("~=", MarkerOperator::TildeEqual),
+        ("<=", MarkerOperator::LessThanEqual),
+        (">=", MarkerOperator::GreaterThanEqual),
+        ("<", MarkerOperator::LessThan),
+        (">", MarkerOperator::GreaterThan),
+    ];
+    if let Some(&(_, operator)) = OPERATORS.iter().find(|(s, _)| cursor.eat(s)) {
+        return Ok(operator);
+    }
+    if cursor.eat_keyword("in") {
+        return Ok(MarkerOperator::In);
+    }
+    let start = cursor.pos();
+    if cursor.eat_keyword("not") {
+        cursor.eat_whitespace();
+        if cursor.eat_keyword("in") {
+            return Ok(MarkerOperator::NotIn);
+        }
+        return Err(cursor.error("expected `in` after `not`", start, cursor.pos() - start));
+    }
+    Err(cursor.error_here("expected a marker operator"))
+}
+
+impl FromStr for MarkerTree {
+    type Err = Error;
+
+    /// Parse a complete marker, e.g. `python_version >= "3.8" and os_name == "nt"`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cursor = Cursor::new(s);
+        let marker = Self::parse(&mut cursor).map_err(Error::InvalidMarker)?;
+        cursor.eat_whitespace();
+        if !cursor.at_end() {
+            return Err(Error::InvalidMarker(cursor.error(
+                "expected `and`, `or` or end of input",
+                cursor.pos(),
+                cursor.rest().len(),
+            )));
+        }
+        Ok(marker)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MarkerEnvironment, MarkerExpression, MarkerOperator, MarkerTree, MarkerValue};
+    use crate::parser::{Cursor, ParseError};
+    use crate::Error;
+
+    fn parse(s: &str) -> Result<MarkerTree, ParseError> {
+        let mut cursor = Cursor::new(s);
+        let marker = MarkerTree::parse(&mut cursor)?;
+        cursor.eat_whitespace();
+        assert!(cursor.at_end(), "{s}");
+        Ok(marker)
+    }
+
+    #[test]
+    fn test_parse_marker() {
+        let marker = parse("python_version >= '3.8'").unwrap();
+        assert_eq!(
+            marker,
+            MarkerTree::Expression(MarkerExpression {
+                left: MarkerValue::Variable("python_version".to_string()),
+                operator: MarkerOperator::GreaterThanEqual,
+                right: MarkerValue::String("3.8".to_string()),
+            })
+        );
+        assert_eq!(marker.to_string(), "python_version >= \"3.8\"");
+
+        let marker = parse(
+            "os.name=='nt' and (extra == \"test\" or 'linux' not in sys_platform) or python_implementation==\"PyPy\"",
+        )
+        .unwrap();
+        assert_eq!(
+            marker.to_string(),
+            "os_name == \"nt\" and (extra == \"test\" or \"linux\" not in sys_platform) or platform_python_implementation == \"PyPy\""
+        );
+        assert!(matches!(marker, MarkerTree::Or(ref markers) if markers.len() == 2));
+    }
+
+    #[test]
+    fn test_evaluate_marker() {
+        let env = MarkerEnvironment {
+            os_name: "posix".to_string(),
+            platform_machine: "x86_64".to_string(),
+            platform_python_implementation: "CPython".to_string(),
+            platform_release: "6.1.0-18-amd64".to_string(),
+            python_full_version: "3.10.12".to_string(),
+            python_version: "3.10".to_string(),
+            sys_platform: "linux".to_string(),
+            ..MarkerEnvironment::default()
+        };
+        for (marker, extras, expected) in [
+            ("python_version >= '3.8'", &[][..], true),
+            ("python_version < '3.9'", &[], false),
+            ("python_version == '3.*'", &[], true),
+            ("'3.9' < python_version", &[], true),
+            ("python_full_version ~= '3.10.0'", &[], true),
+            ("os_name == 'nt'", &[], false),
+            ("os_name != 'nt' and sys_platform == 'linux'", &[], true),
+            ("'linux' in sys_platform", &[], true),
+            ("'x86' not in platform_machine", &[], false),
+            ("platform_release >= '6'", &[], true),
+            ("extra == 'test'", &[], false),
+            ("extra == 'test'", &["test"], true),
+            ("extra == 'Foo_Bar'", &["foo-bar"], true),
+            ("extra == 'test' and os_name == 'nt'", &["test"], false),
+            ("extra == 'test' or os_name == 'nt'", &["docs"], false),
+            (
+                "(extra == 'a' or extra == 'b') and python_version >= '3'",
+                &["b"],
+                true,
+            ),
+        ] {
+            let tree = parse(marker).unwrap();
+            assert_eq!(tree.evaluate(&env, extras), expected, "{marker} {extras:?}");
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_marker() {
+        let err = parse("python_versio >= '3.8'").unwrap_err();
+        assert_eq!((err.start, err.len), (0, 13));
+        let err = parse("python_version >= '3.8").unwrap_err();
+        assert_eq!((err.start, err.len), (18, 4));
+        let err = parse("python_version ~ '3.8'").unwrap_err();
+        assert_eq!((err.start, err.len), (15, 1));
+        let err = parse("'a' not 'b'").unwrap_err();
+        assert_eq!((err.start, err.len), (4, 4));
+        let err = parse("(os_name == 'nt'").unwrap_err();
+        assert_eq!(err.start, 16);
+        let err = parse("os_name == 'nt' and").unwrap_err();
+        assert_eq!(err.start, 19);
+    }
+
+    #[test]
+    fn test_marker_from_str() {
+        let marker: MarkerTree = "os_name == 'nt' or python_version < '3'".parse().unwrap();
+        assert_eq!(
+            marker.and(MarkerTree::extra("test")).to_string(),
+            "(os_name == \"nt\" or python_version < \"3\") and extra == \"test\""
+        );
+        let result: Result<MarkerTree, Error> = "os_name == 'nt' extra".parse();
+        assert!(matches!(result, Err(Error::InvalidMarker(_))));
+    }
+}
@@ -1,16 +1,155 @@
+use std::cmp::Ordering;
+use std::convert::Infallible;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 
 use mailparse::MailHeaderMap;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::Error;
+use crate::{Error, MarkerEnvironment, PackageName, Requirement, Version, VersionSpecifiers};
+
+/// Version of the core metadata specification a metadata file conforms to
+///
+/// Versions are ordered numerically, unknown versions that aren't `major.minor`
+/// sort before all others. With the `serde` feature, it's (de)serialized as a string.
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(from = "String", into = "String"))]
+#[derive(Debug, Clone)]
+pub enum MetadataVersion {
+    /// Metadata-Version 1.0, PEP 241
+    V1_0,
+    /// Metadata-Version 1.1, PEP 314
+    V1_1,
+    /// Metadata-Version 1.2, PEP 345
+    V1_2,
+    /// Metadata-Version 2.0, from the withdrawn PEP 426 but written by older tools
+    V2_0,
+    /// Metadata-Version 2.1, PEP 566
+    V2_1,
+    /// Metadata-Version 2.2, PEP 643
+    V2_2,
+    /// Metadata-Version 2.3, PEP 685
+    V2_3,
+    /// Metadata-Version 2.4, PEP 639
+    V2_4,
+    /// Any other version, as written
+    Unknown(String),
+}
+
+impl MetadataVersion {
+    /// The newest version known to this library
+    pub const LATEST: MetadataVersion = MetadataVersion::V2_4;
+
+    /// Returns the `(major, minor)` version, `None` for unknown versions that aren't `major.minor`
+    pub fn major_minor(&self) -> Option<(u64, u64)> {
+        match self {
+            MetadataVersion::V1_0 => Some((1, 0)),
+            MetadataVersion::V1_1 => Some((1, 1)),
+            MetadataVersion::V1_2 => Some((1, 2)),
+            MetadataVersion::V2_0 => Some((2, 0)),
+            MetadataVersion::V2_1 => Some((2, 1)),
+            MetadataVersion::V2_2 => Some((2, 2)),
+            MetadataVersion::V2_3 => Some((2, 3)),
+            MetadataVersion::V2_4 => Some((2, 4)),
+            MetadataVersion::Unknown(version) => {
+                let (major, minor) = version.split_once('.')?;
+                Some((major.parse().ok()?, minor.parse().ok()?))
+            }
+        }
+    }
+
+    /// Returns `true` if the major version is known to this library
+    ///
+    /// The specification requires tools to reject metadata with a newer major version,
+    /// a newer minor version is backwards compatible.
+    pub fn is_supported(&self) -> bool {
+        self.major_minor()
+            .is_some_and(|(major, _)| (1..=2).contains(&major))
+    }
+}
+
+impl From<&str> for MetadataVersion {
+    fn from(version: &str) -> Self {
+        match version.trim() {
+            "1.0" => MetadataVersion::V1_0,
+            "1.1" => MetadataVersion::V1_1,
+            "1.2" => MetadataVersion::V1_2,
+            "2.0" => MetadataVersion::V2_0,
+            "2.1" => MetadataVersion::V2_1,
+            "2.2" => MetadataVersion::V2_2,
+            "2.3" => MetadataVersion::V2_3,
+            "2.4" => MetadataVersion::V2_4,
+            version => MetadataVersion::Unknown(version.to_string()),
+        }
+    }
+}
+
+impl From<String> for MetadataVersion {
+    fn from(version: String) -> Self {
+        version.as_str().into()
+    }
+}
+
+impl From<MetadataVersion> for String {
+    fn from(version: MetadataVersion) -> Self {
+        version.to_string()
+    }
+}
+
+impl FromStr for MetadataVersion {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(s.into())
+    }
+}
+
+impl fmt::Display for MetadataVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self, self.major_minor()) {
+            (MetadataVersion::Unknown(version), _) => write!(f, "{version}"),
+            (_, Some((major, minor))) => write!(f, "{major}.{minor}"),
+            (_, None) => unreachable!("known versions are major.minor"),
+        }
+    }
+}
+
+impl Ord for MetadataVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.major_minor()
+            .cmp(&other.major_minor())
+            .then_with(|| self.to_string().cmp(&other.to_string()))
+    }
+}
+
+impl PartialOrd for MetadataVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for MetadataVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for MetadataVersion {}
+
+impl Hash for MetadataVersion {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_string().hash(state);
+    }
+}
 
 /// Python package metadata
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct Metadata {
-    /// Version of the file format; legal values are `1.0`, `1.1`, `1.2`, `2.1` and `2.2`.
+    /// Version of the file format; legal values are `1.0`, `1.1`, `1.2`, `2.1`, `2.2`, `2.3`
+    /// and `2.4`, see [`MetadataVersion`].
     pub metadata_version: String,
     /// The name of the distribution.
     pub name: String,
@@ -101,11 +240,117 @@ pub struct Metadata {
     /// A string containing the name of another core metadata field.
     #[cfg_attr(feature = "serde", serde(default))]
     pub dynamic: Vec<String>,
+    /// Headers without a corresponding field, e.g. `Requires` from Metadata-Version 1.1,
+    /// as name and value pairs in the order they appear.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub extra_headers: Vec<(String, String)>,
+}
+
+/// A problem found by [`Metadata::parse_with_diagnostics`] that didn't prevent parsing
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ParseWarning {
+    /// A header value with invalid RFC 2047 encoded words, the field was ignored
+    UndecodableHeader {
+        /// The header name.
+        name: String,
+        /// The raw header value.
+        value: String,
+    },
+    /// A single-use field that was specified more than once, only the first value was used
+    DuplicateField(String),
+    /// A field with the `UNKNOWN` placeholder value, which was treated as missing
+    UnknownPlaceholder(String),
+    /// Both a `Description` header and a message body, the body was used
+    DescriptionConflict,
+    /// A `Metadata-Version` unknown to this library, whose new fields end up in
+    /// [`Metadata::extra_headers`]
+    ///
+    /// A newer minor version is backwards compatible, a newer major version is rejected
+    /// by [`Metadata::parse_strict`].
+    UnknownMetadataVersion(String),
 }
 
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseWarning::UndecodableHeader { name, value } => {
+                write!(f, "{name}: undecodable value `{value}`")
+            }
+            ParseWarning::DuplicateField(name) => write!(f, "{name}: specified more than once"),
+            ParseWarning::UnknownPlaceholder(name) => {
+                write!(f, "{name}: `UNKNOWN` placeholder value")
+            }
+            ParseWarning::DescriptionConflict => {
+                write!(f, "Description: both a header and a message body")
+            }
+            ParseWarning::UnknownMetadataVersion(version) => {
+                write!(f, "Metadata-Version: unknown version `{version}`")
+            }
+        }
+    }
+}
+
+/// Fields that may appear at most once
+const SINGLE_USE_FIELDS: &[&str] = &[
+    "Metadata-Version",
+    "Name",
+    "Version",
+    "Summary",
+    "Description",
+    "Description-Content-Type",
+    "Keywords",
+    "Home-page",
+    "Download-URL",
+    "Author",
+    "Author-email",
+    "Maintainer",
+    "Maintainer-email",
+    "License",
+    "License-Expression",
+    "Requires-Python",
+];
+
+/// Headers parsed into the fields of [`Metadata`]
+const KNOWN_HEADERS: &[&str] = &[
+    "Metadata-Version",
+    "Name",
+    "Version",
+    "Dynamic",
+    "Platform",
+    "Supported-Platform",
+    "Summary",
+    "Description",
+    "Description-Content-Type",
+    "Keywords",
+    "Home-Page",
+    "Download-URL",
+    "Author",
+    "Author-email",
+    "Maintainer",
+    "Maintainer-email",
+    "License",
+    "License-Expression",
+    "License-File",
+    "Classifier",
+    "Requires-Dist",
+    "Requires-Python",
+    "Requires-External",
+    "Project-URL",
+    "Provides-Extra",
+    "Provides-Dist",
+    "Obsoletes-Dist",
+];
+
 impl Metadata {
     /// Parse distribution metadata from metadata bytes
     pub fn parse(content: &[u8]) -> Result<Self, Error> {
+        Self::parse_with_diagnostics(content).map(|(metadata, _)| metadata)
+    }
+
+    /// Parse distribution metadata from metadata bytes, also returning the problems
+    /// that the lenient parser otherwise ignores
+    pub fn parse_with_diagnostics(content: &[u8]) -> Result<(Self, Vec<ParseWarning>), Error> {
         // HACK: trick mailparse to parse as UTF-8 instead of ASCII
         let mut mail = b"Content-Type: text/plain; charset=utf-8\n".to_vec();
         mail.extend_from_slice(content);
@@ -113,18 +358,10 @@ impl Metadata {
         let msg = mailparse::parse_mail(&mail)?;
         let headers = msg.get_headers();
         let get_first_value = |name| {
-            headers.get_first_header(name).and_then(|header| {
-                match rfc2047_decoder::decode(header.get_value_raw()) {
-                    Ok(value) => {
-                        if value == "UNKNOWN" {
-                            None
-                        } else {
-                            Some(value)
-                        }
-                    }
-                    Err(_) => None,
-                }
-            })
+            headers
+                .get_first_header(name)
+                .and_then(|header| rfc2047_decoder::decode(header.get_value_raw()).ok())
+                .filter(|value| value != "UNKNOWN")
         };
         let get_all_values = |name| {
             let values: Vec<String> = headers
@@ -147,10 +384,11 @@ impl Metadata {
         let supported_platforms = get_all_values("Supported-Platform");
         let summary = get_first_value("Summary");
         let body = msg.get_body()?;
-        let description = if !body.trim().is_empty() {
+        let has_body = !body.trim().is_empty();
+        let description = if has_body {
             Some(body)
         } else {
-            get_first_value("Description")
+            get_first_value("Description").map(|value| unfold_description(&value))
         };
         let keywords = get_first_value("Keywords");
         let home_page = get_first_value("Home-Page");
@@ -172,7 +410,49 @@ impl Metadata {
         let provides_extras = get_all_values("Provides-Extra");
         let description_content_type = get_first_value("Description-Content-Type");
         let dynamic = get_all_values("Dynamic");
-        Ok(Metadata {
+        let mut warnings = Vec::new();
+        if let MetadataVersion::Unknown(version) = MetadataVersion::from(metadata_version.as_str())
+        {
+            warnings.push(ParseWarning::UnknownMetadataVersion(version));
+        }
+        for field in SINGLE_USE_FIELDS {
+            if headers.get_all_headers(field).len() > 1 {
+                warnings.push(ParseWarning::DuplicateField(field.to_string()));
+            }
+        }
+        // Skip the `Content-Type` header added above
+        for header in msg.headers.iter().skip(1) {
+            let name = header.get_key();
+            if !KNOWN_HEADERS
+                .iter()
+                .any(|known| known.eq_ignore_ascii_case(&name))
+            {
+                continue;
+            }
+            if rfc2047_decoder::decode(header.get_value_raw()).is_err() {
+                let value = String::from_utf8_lossy(header.get_value_raw()).into_owned();
+                warnings.push(ParseWarning::UndecodableHeader { name, value });
+            } else if header.get_value() == "UNKNOWN" {
+                warnings.push(ParseWarning::UnknownPlaceholder(name));
+            }
+        }
+        if has_body && headers.get_first_header("Description").is_some() {
+            warnings.push(ParseWarning::DescriptionConflict);
+        }
+        let extra_headers = msg
+            .headers
+            .iter()
+            // Skip the `Content-Type` header added above
+            .skip(1)
+            .filter(|header| {
+                let key = header.get_key_ref();
+                !KNOWN_HEADERS
+                    .iter()
+                    .any(|known| known.eq_ignore_ascii_case(&key))
+            })
+            .map(|header| (header.get_key(), header.get_value()))
+            .collect();
+        let metadata = Metadata {
             metadata_version,
             name,
             version,
@@ -200,7 +480,192 @@ impl Metadata {
             provides_extras,
             description_content_type,
             dynamic,
-        })
+            extra_headers,
+        };
+        Ok((metadata, warnings))
+    }
+}
+
+impl Metadata {
+    /// Parse [`Metadata::metadata_version`]
+    pub fn parsed_metadata_version(&self) -> MetadataVersion {
+        self.metadata_version.as_str().into()
+    }
+
+    /// Parse [`Metadata::name`] as a [`PackageName`]
+    pub fn package_name(&self) -> Result<PackageName, Error> {
+        PackageName::new(self.name.as_str())
+    }
+
+    /// Parse [`Metadata::version`] as a PEP 440 version
+    ///
+    /// Returns [`Error::InvalidVersion`] for legacy versions that don't comply with PEP 440.
+    pub fn parsed_version(&self) -> Result<Version, Error> {
+        self.version.parse()
+    }
+
+    /// Parse [`Metadata::requires_python`] as PEP 440 version specifiers
+    pub fn requires_python_specifiers(&self) -> Result<Option<VersionSpecifiers>, Error> {
+        self.requires_python.as_deref().map(str::parse).transpose()
+    }
+
+    /// Parse [`Metadata::requires_dist`] as PEP 508 requirements
+    pub fn requirements(&self) -> Result<Vec<Requirement>, Error> {
+        self.requires_dist.iter().map(|s| s.parse()).collect()
+    }
+
+    /// Returns the requirements that apply to `env` when installing with `extras`
+    pub fn requirements_for(
+        &self,
+        env: &MarkerEnvironment,
+        extras: &[&str],
+    ) -> Result<Vec<Requirement>, Error> {
+        let mut requirements = self.requirements()?;
+        requirements.retain(|requirement| requirement.evaluate_markers(env, extras));
+        Ok(requirements)
+    }
+
+    /// Parse [`Metadata::provides_dist`] as PEP 508 requirements
+    pub fn provided_distributions(&self) -> Result<Vec<Requirement>, Error> {
+        self.provides_dist.iter().map(|s| s.parse()).collect()
+    }
+
+    /// Parse [`Metadata::obsoletes_dist`] as PEP 508 requirements
+    pub fn obsoleted_distributions(&self) -> Result<Vec<Requirement>, Error> {
+        self.obsoletes_dist.iter().map(|s| s.parse()).collect()
+    }
+}
+
+impl Metadata {
+    /// Returns `true` if the description is stored in the message body, which
+    /// Metadata-Version 2.1 introduced
+    fn description_in_body(&self) -> bool {
+        self.parsed_metadata_version() >= MetadataVersion::V2_1
+    }
+}
+
+/// Strip the continuation line prefix setuptools adds to a multi-line `Description` header
+fn unfold_description(value: &str) -> String {
+    let mut lines = value.split('\n');
+    let mut description = lines.next().unwrap_or_default().to_string();
+    for line in lines {
+        description.push('\n');
+        let line = line
+            .strip_prefix("       |")
+            .or_else(|| line.strip_prefix("        "))
+            .unwrap_or(line);
+        description.push_str(line);
+    }
+    description
+}
+
+/// Returns the `Metadata-Version` that introduced `field`, 1.0 for the original fields
+pub(crate) fn field_introduced_in(field: &str) -> MetadataVersion {
+    match field {
+        "Supported-Platform" | "Download-URL" | "Classifier" => MetadataVersion::V1_1,
+        "Maintainer" | "Maintainer-email" | "Requires-Dist" | "Provides-Dist"
+        | "Obsoletes-Dist" | "Requires-Python" | "Requires-External" | "Project-URL" => {
+            MetadataVersion::V1_2
+        }
+        "Description-Content-Type" | "Provides-Extra" => MetadataVersion::V2_1,
+        "Dynamic" => MetadataVersion::V2_2,
+        "License-Expression" | "License-File" => MetadataVersion::V2_4,
+        _ => MetadataVersion::V1_0,
+    }
+}
+
+/// Write a header, folding multi-line values with continuation lines
+///
+/// `Description` is folded like setuptools does, so that parsing strips the prefix again.
+/// Other headers keep their line breaks and indentation as parsed, only continuation lines
+/// that don't start with whitespace get indented.
+fn write_header(f: &mut fmt::Formatter<'_>, name: &str, value: &str) -> fmt::Result {
+    let mut lines = value.split('\n');
+    write!(f, "{}: {}", name, lines.next().unwrap_or_default())?;
+    for line in lines {
+        if name == "Description" {
+            write!(f, "\n       |{line}")?;
+        } else if line.starts_with([' ', '\t']) {
+            write!(f, "\n{line}")?;
+        } else {
+            write!(f, "\n        {line}")?;
+        }
+    }
+    writeln!(f)
+}
+
+impl fmt::Display for Metadata {
+    /// Serialize to the core metadata format used by `METADATA` and `PKG-INFO` files
+    ///
+    /// The fields are written in the same order for every `Metadata-Version`: the order of
+    /// the core metadata specification, with `Dynamic` after `Version`, followed by
+    /// [`Metadata::extra_headers`] and a 2.1+ description as the message body.
+    /// Fields introduced after the `Metadata-Version` are left out, unless the version
+    /// isn't `major.minor`. `License-File` is always kept.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let metadata_version = self.parsed_metadata_version();
+        let is_defined = |name| {
+            // setuptools wrote `License-File` long before 2.4 standardized it
+            name == "License-File"
+                || metadata_version.major_minor().is_none()
+                || metadata_version >= field_introduced_in(name)
+        };
+        let single = |f: &mut fmt::Formatter<'_>, name, value: &Option<String>| match value {
+            Some(value) if is_defined(name) => write_header(f, name, value),
+            _ => Ok(()),
+        };
+        let multiple = |f: &mut fmt::Formatter<'_>, name, values: &[String]| {
+            if !is_defined(name) {
+                return Ok(());
+            }
+            values
+                .iter()
+                .try_for_each(|value| write_header(f, name, value))
+        };
+        let description_in_body = self.description_in_body();
+
+        write_header(f, "Metadata-Version", &self.metadata_version)?;
+        write_header(f, "Name", &self.name)?;
+        write_header(f, "Version", &self.version)?;
+        multiple(f, "Dynamic", &self.dynamic)?;
+        multiple(f, "Platform", &self.platforms)?;
+        multiple(f, "Supported-Platform", &self.supported_platforms)?;
+        single(f, "Summary", &self.summary)?;
+        if !description_in_body {
+            single(f, "Description", &self.description)?;
+        }
+        single(
+            f,
+            "Description-Content-Type",
+            &self.description_content_type,
+        )?;
+        single(f, "Keywords", &self.keywords)?;
+        single(f, "Home-page", &self.home_page)?;
+        single(f, "Download-URL", &self.download_url)?;
+        single(f, "Author", &self.author)?;
+        single(f, "Author-email", &self.author_email)?;
+        single(f, "Maintainer", &self.maintainer)?;
+        single(f, "Maintainer-email", &self.maintainer_email)?;
+        single(f, "License", &self.license)?;
+        single(f, "License-Expression", &self.license_expression)?;
+        multiple(f, "License-File", &self.license_files)?;
+        multiple(f, "Classifier", &self.classifiers)?;
+        multiple(f, "Requires-Dist", &self.requires_dist)?;
+        single(f, "Requires-Python", &self.requires_python)?;
+        multiple(f, "Requires-External", &self.requires_external)?;
+        multiple(f, "Project-URL", &self.project_urls)?;
+        multiple(f, "Provides-Extra", &self.provides_extras)?;
+        multiple(f, "Provides-Dist", &self.provides_dist)?;
+        multiple(f, "Obsoletes-Dist", &self.obsoletes_dist)?;
+        for (name, value) in &self.extra_headers {
+            write_header(f, name, value)?;
+        }
+        if description_in_body {
+            if let Some(description) = &self.description {
+                write!(f, "\n{description}")?;
+            }
+        }
+        Ok(())
     }
 }
 
@@ -214,8 +679,8 @@ impl FromStr for Metadata {
 
 #[cfg(test)]
 mod tests {
-    use super::Metadata;
-    use crate::Error;
+    use super::{Metadata, MetadataVersion, ParseWarning};
+    use crate::{Error, MarkerEnvironment};
 
     #[test]
     fn test_parse_from_str() {
@@ -247,10 +712,264 @@ mod tests {
         assert_eq!(meta.description.as_deref(), Some("一个 Python 包"));
     }
 
+    #[test]
+    fn test_to_string() {
+        let meta = Metadata {
+            metadata_version: "2.1".to_string(),
+            name: "asdf".to_string(),
+            version: "1.0".to_string(),
+            summary: Some("a Python package".to_string()),
+            description: Some("# asdf\n\nMore details.\n".to_string()),
+            classifiers: vec![
+                "Programming Language :: Python :: 3".to_string(),
+                "License :: OSI Approved :: MIT License".to_string(),
+            ],
+            requires_dist: vec!["packaging>=19.0".to_string()],
+            ..Metadata::default()
+        };
+        let s = meta.to_string();
+        assert_eq!(
+            s,
+            "Metadata-Version: 2.1\nName: asdf\nVersion: 1.0\nSummary: a Python package\nClassifier: Programming Language :: Python :: 3\nClassifier: License :: OSI Approved :: MIT License\nRequires-Dist: packaging>=19.0\n\n# asdf\n\nMore details.\n"
+        );
+        assert_eq!(s.parse::<Metadata>().unwrap(), meta);
+
+        // Before 2.1 the description is a folded header
+        let meta = Metadata {
+            metadata_version: "1.2".to_string(),
+            ..meta
+        };
+        let s = meta.to_string();
+        assert!(s.contains("Description: # asdf\n       |\n       |More details.\n       |\n"));
+        assert_eq!(s.parse::<Metadata>().unwrap(), meta);
+
+        // Other multi-line headers keep their continuation lines as parsed
+        let s = "Metadata-Version: 1.2\nName: asdf\nVersion: 1.0\nLicense: MIT License\n        \n        Copyright (c) asdf\n";
+        let parsed: Metadata = s.parse().unwrap();
+        assert_eq!(
+            parsed.license.as_deref(),
+            Some("MIT License\n        \n        Copyright (c) asdf")
+        );
+        let meta = Metadata {
+            license: parsed.license,
+            ..meta
+        };
+        let s = meta.to_string();
+        assert!(s.contains("License: MIT License\n        \n        Copyright (c) asdf\n"));
+        assert_eq!(s.parse::<Metadata>().unwrap(), meta);
+        let meta = Metadata {
+            keywords: Some("a,\nb".to_string()),
+            ..meta
+        };
+        assert!(meta.to_string().contains("Keywords: a,\n        b\n"));
+
+        // Fields the `Metadata-Version` doesn't define are left out
+        let meta = Metadata {
+            metadata_version: "1.0".to_string(),
+            requires_dist: vec!["packaging>=19.0".to_string()],
+            dynamic: vec!["License".to_string()],
+            license_expression: Some("MIT".to_string()),
+            ..meta
+        };
+        let s = meta.to_string();
+        assert!(s.contains("License: MIT License\n"));
+        for field in [
+            "Classifier",
+            "Requires-Dist",
+            "Dynamic",
+            "License-Expression",
+        ] {
+            assert!(!s.contains(&format!("{field}:")), "{field}");
+        }
+        let meta = Metadata {
+            metadata_version: "2.4".to_string(),
+            ..meta
+        };
+        let s = meta.to_string();
+        assert!(s.contains("Dynamic: License\n") && s.contains("License-Expression: MIT\n"));
+    }
+
+    #[test]
+    fn test_extra_headers() {
+        let s = "Metadata-Version: 1.1\nName: asdf\nVersion: 1.0\nRequires: os\nProvides: asdf\nrequires: sys\nX-Vendor-Field: UNKNOWN\nClassifier: Private :: Do Not Upload";
+        let meta: Metadata = s.parse().unwrap();
+        assert_eq!(
+            meta.extra_headers,
+            [
+                ("Requires".to_string(), "os".to_string()),
+                ("Provides".to_string(), "asdf".to_string()),
+                ("requires".to_string(), "sys".to_string()),
+                ("X-Vendor-Field".to_string(), "UNKNOWN".to_string()),
+            ]
+        );
+        assert_eq!(meta.classifiers, ["Private :: Do Not Upload"]);
+        assert_eq!(meta.to_string().parse::<Metadata>().unwrap(), meta);
+
+        let s = "Metadata-Version: 2.1\nName: asdf\nVersion: 1.0\nhome-page: https://example.com";
+        let meta: Metadata = s.parse().unwrap();
+        assert!(meta.extra_headers.is_empty());
+    }
+
+    #[test]
+    fn test_parse_with_diagnostics() {
+        let s = "Metadata-Version: 2.5\nName: asdf\nVersion: 1.0\nSummary: one\nSummary: two\nLicense: UNKNOWN\nAuthor: =?utf-8?B?invalid?=\nDescription: header\nX-Vendor: UNKNOWN\n\nbody";
+        let (meta, warnings) = Metadata::parse_with_diagnostics(s.as_bytes()).unwrap();
+        assert_eq!(meta.summary.as_deref(), Some("one"));
+        assert_eq!(meta.description.as_deref(), Some("body"));
+        assert_eq!(
+            warnings,
+            [
+                ParseWarning::UnknownMetadataVersion("2.5".to_string()),
+                ParseWarning::DuplicateField("Summary".to_string()),
+                ParseWarning::UnknownPlaceholder("License".to_string()),
+                ParseWarning::UndecodableHeader {
+                    name: "Author".to_string(),
+                    value: "=?utf-8?B?invalid?=".to_string()
+                },
+                ParseWarning::DescriptionConflict,
+            ]
+        );
+        assert_eq!(warnings[1].to_string(), "Summary: specified more than once");
+
+        for version in ["2.0", "2.1", "2.4"] {
+            let s = format!("Metadata-Version: {version}\nName: asdf\nVersion: 1.0\n\nbody");
+            let (_, warnings) = Metadata::parse_with_diagnostics(s.as_bytes()).unwrap();
+            assert!(warnings.is_empty(), "{version}");
+        }
+
+        // Newer major versions are still read by the lenient parser
+        let s = "Metadata-Version: 3.0\nName: asdf\nVersion: 1.0\n";
+        let (meta, warnings) = Metadata::parse_with_diagnostics(s.as_bytes()).unwrap();
+        assert_eq!(meta.metadata_version, "3.0");
+        assert_eq!(
+            warnings,
+            [ParseWarning::UnknownMetadataVersion("3.0".to_string())]
+        );
+        assert!(Metadata::parse(s.as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn test_metadata_version() {
+        let s = "Metadata-Version: 2.2\nName: asdf\nVersion: 1.0";
+        let meta: Metadata = s.parse().unwrap();
+        let version = meta.parsed_metadata_version();
+        assert_eq!(version, MetadataVersion::V2_2);
+        assert!(version >= MetadataVersion::V2_2);
+        assert!(version < MetadataVersion::LATEST);
+
+        let mut versions: Vec<MetadataVersion> = ["2.10", "1.0", "3.0", "2.1", "foo", "2.5"]
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        versions.sort();
+        let versions: Vec<_> = versions.iter().map(ToString::to_string).collect();
+        assert_eq!(versions, ["foo", "1.0", "2.1", "2.5", "2.10", "3.0"]);
+
+        assert_eq!(MetadataVersion::from("2.0"), MetadataVersion::V2_0);
+        assert!(MetadataVersion::V2_0 < MetadataVersion::V2_1);
+        assert!(MetadataVersion::from("2.5").is_supported());
+        assert!(!MetadataVersion::from("3.0").is_supported());
+        assert!(!MetadataVersion::from("foo").is_supported());
+        assert_eq!(
+            MetadataVersion::from(" 1.1 "),
+            "1.1".parse::<MetadataVersion>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parsed_version() {
+        let s = "Metadata-Version: 1.0\nName: asdf\nVersion: 1.0-RC1";
+        let meta: Metadata = s.parse().unwrap();
+        assert_eq!(meta.parsed_version().unwrap().to_string(), "1.0rc1");
+
+        let s = "Metadata-Version: 1.0\nName: asdf\nVersion: 2004d";
+        let meta: Metadata = s.parse().unwrap();
+        assert!(matches!(
+            meta.parsed_version(),
+            Err(Error::InvalidVersion { .. })
+        ));
+    }
+
+    #[test]
+    fn test_requirements() {
+        let s = "Metadata-Version: 2.1\nName: asdf\nVersion: 1.0\nRequires-Dist: packaging (>=19.0)\nRequires-Dist: colorama; os_name == \"nt\"\nProvides-Dist: asdf_compat\nObsoletes-Dist: asdf-legacy (<1.0)";
+        let meta: Metadata = s.parse().unwrap();
+        let requirements = meta.requirements().unwrap();
+        assert_eq!(requirements.len(), 2);
+        assert_eq!(requirements[0].to_string(), "packaging>=19.0");
+        assert_eq!(requirements[1].name, "colorama");
+        assert!(requirements[1].marker.is_some());
+        assert_eq!(
+            meta.provided_distributions().unwrap()[0].name,
+            "asdf_compat"
+        );
+        assert_eq!(
+            meta.obsoleted_distributions().unwrap()[0].to_string(),
+            "asdf-legacy<1.0"
+        );
+
+        let s =
+            "Metadata-Version: 2.1\nName: asdf\nVersion: 1.0\nRequires-Dist: packaging >= 19.0 foo";
+        let meta: Metadata = s.parse().unwrap();
+        assert!(matches!(
+            meta.requirements(),
+            Err(Error::InvalidRequirement(err)) if err.start == 18
+        ));
+    }
+
+    #[test]
+    fn test_requires_python_specifiers() {
+        let s = "Metadata-Version: 1.2\nName: asdf\nVersion: 1.0\nRequires-Python: >=3.8, !=3.9.*";
+        let meta: Metadata = s.parse().unwrap();
+        let specifiers = meta.requires_python_specifiers().unwrap().unwrap();
+        assert_eq!(specifiers.to_string(), ">=3.8,!=3.9.*");
+
+        let s = "Metadata-Version: 1.2\nName: asdf\nVersion: 1.0";
+        let meta: Metadata = s.parse().unwrap();
+        assert!(meta.requires_python_specifiers().unwrap().is_none());
+
+        let s = "Metadata-Version: 1.2\nName: asdf\nVersion: 1.0\nRequires-Python: 3.8";
+        let meta: Metadata = s.parse().unwrap();
+        assert!(matches!(
+            meta.requires_python_specifiers(),
+            Err(Error::InvalidSpecifier(_))
+        ));
+    }
+
+    #[test]
+    fn test_requirements_for() {
+        let s = "Metadata-Version: 2.1\nName: asdf\nVersion: 1.0\nRequires-Dist: packaging\nRequires-Dist: colorama; os_name == \"nt\"\nRequires-Dist: tomli; python_version < \"3.11\"\nRequires-Dist: pytest; extra == \"test\"";
+        let meta: Metadata = s.parse().unwrap();
+        let names = |env: &MarkerEnvironment, extras: &[&str]| -> Vec<String> {
+            meta.requirements_for(env, extras)
+                .unwrap()
+                .into_iter()
+                .map(|req| req.name)
+                .collect()
+        };
+        let linux = MarkerEnvironment {
+            os_name: "posix".to_string(),
+            python_version: "3.12".to_string(),
+            ..MarkerEnvironment::default()
+        };
+        assert_eq!(names(&linux, &[]), ["packaging"]);
+        assert_eq!(names(&linux, &["test"]), ["packaging", "pytest"]);
+        let windows = MarkerEnvironment {
+            os_name: "nt".to_string(),
+            python_version: "3.8".to_string(),
+            ..MarkerEnvironment::default()
+        };
+        assert_eq!(names(&windows, &[]), ["packaging", "colorama", "tomli"]);
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn test_serde_deserialize() {
         let input = r#"{"metadata_version": "2.3", "name": "example", "version": "1.0.0"}"#;
-        let _metadata: Metadata = serde_json::from_str(input).unwrap();
+        let metadata: Metadata = serde_json::from_str(input).unwrap();
+
+        let version: MetadataVersion = serde_json::from_str(r#""2.3""#).unwrap();
+        assert_eq!(version, metadata.parsed_metadata_version());
+        assert_eq!(serde_json::to_string(&version).unwrap(), r#""2.3""#);
     }
 }
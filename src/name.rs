@@ -0,0 +1,136 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Error;
+
+/// PEP 503 name normalization, also used for extras by PEP 685
+///
+/// Lowercases the name and replaces runs of `-`, `_` and `.` with a single `-`.
+pub(crate) fn normalize(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    for c in name.chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !normalized.ends_with('-') {
+                normalized.push('-');
+            }
+        } else {
+            normalized.push(c.to_ascii_lowercase());
+        }
+    }
+    normalized
+}
+
+/// Check a distribution name or extra: `^([A-Z0-9]|[A-Z0-9][A-Z0-9._-]*[A-Z0-9])$`, case-insensitively
+pub(crate) fn is_valid(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && name.ends_with(|c: char| c.is_ascii_alphanumeric())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c))
+}
+
+/// A valid distribution name that compares equal to other spellings of the same name
+///
+/// `Foo_Bar`, `foo-bar` and `foo.bar` are the same package, see
+/// [PEP 503](https://peps.python.org/pep-0503/#normalized-names).
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[derive(Debug, Clone)]
+pub struct PackageName(String);
+
+impl PackageName {
+    /// Create a package name, failing if it isn't a valid distribution name
+    pub fn new(name: impl Into<String>) -> Result<Self, Error> {
+        let name = name.into();
+        if is_valid(&name) {
+            Ok(Self(name))
+        } else {
+            Err(Error::InvalidPackageName(name))
+        }
+    }
+
+    /// Returns the name as written
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the PEP 503 normalized name, e.g. `foo-bar` for `Foo_Bar`
+    pub fn normalized(&self) -> String {
+        normalize(&self.0)
+    }
+}
+
+impl PartialEq for PackageName {
+    fn eq(&self, other: &Self) -> bool {
+        self.normalized() == other.normalized()
+    }
+}
+
+impl Eq for PackageName {}
+
+impl PartialEq<str> for PackageName {
+    fn eq(&self, other: &str) -> bool {
+        self.normalized() == normalize(other)
+    }
+}
+
+impl PartialEq<&str> for PackageName {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl Hash for PackageName {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.normalized().hash(state);
+    }
+}
+
+impl AsRef<str> for PackageName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for PackageName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for PackageName {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PackageName;
+    use crate::Error;
+
+    #[test]
+    fn test_package_name() {
+        let name: PackageName = "Foo__Bar.baz".parse().unwrap();
+        assert_eq!(name.as_str(), "Foo__Bar.baz");
+        assert_eq!(name.to_string(), "Foo__Bar.baz");
+        assert_eq!(name.normalized(), "foo-bar-baz");
+        assert_eq!(name, "foo-bar-baz");
+        assert_eq!(name, PackageName::new("FOO.bar_BAZ").unwrap());
+        assert_ne!(name, PackageName::new("foobarbaz").unwrap());
+
+        for name in ["", "-foo", "foo_", "foo bar", "föo"] {
+            let result: Result<PackageName, Error> = name.parse();
+            assert!(
+                matches!(result, Err(Error::InvalidPackageName(_))),
+                "{name}"
+            );
+        }
+    }
+}
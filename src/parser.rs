@@ -0,0 +1,146 @@
+use std::error::Error as StdError;
+use std::fmt;
+
+/// A syntax error in a PEP 508 requirement, marker or version specifier
+///
+/// Carries the location of the problem so it can be pointed out to the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Description of the problem.
+    pub message: String,
+    /// The complete input that failed to parse.
+    pub input: String,
+    /// Offset of the problematic span in characters.
+    pub start: usize,
+    /// Length of the problematic span in characters, at least 1.
+    pub len: usize,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.message)?;
+        writeln!(f, "{}", self.input)?;
+        write!(f, "{}{}", " ".repeat(self.start), "^".repeat(self.len))
+    }
+}
+
+impl StdError for ParseError {}
+
+/// Cursor over the input of the PEP 508 family of parsers
+pub(crate) struct Cursor<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub(crate) fn new(input: &'a str) -> Self {
+        Self { input, pos: 0 }
+    }
+
+    /// Current byte offset
+    pub(crate) fn pos(&self) -> usize {
+        self.pos
+    }
+
+    pub(crate) fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    pub(crate) fn at_end(&self) -> bool {
+        self.pos == self.input.len()
+    }
+
+    pub(crate) fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    pub(crate) fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    pub(crate) fn eat(&mut self, prefix: &str) -> bool {
+        if self.rest().starts_with(prefix) {
+            self.pos += prefix.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consume `keyword` only if it isn't followed by more identifier characters
+    pub(crate) fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let rest = self.rest();
+        match rest.strip_prefix(keyword) {
+            Some(after) if !after.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_') => {
+                self.pos += keyword.len();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub(crate) fn eat_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    /// Consume characters while `predicate` holds and return them
+    pub(crate) fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let start = self.pos;
+        let len = self
+            .rest()
+            .find(|c: char| !predicate(c))
+            .unwrap_or(self.rest().len());
+        self.pos += len;
+        &self.input[start..self.pos]
+    }
+
+    /// Create an error for the byte range `start..start + len`
+    pub(crate) fn error(&self, message: impl Into<String>, start: usize, len: usize) -> ParseError {
+        let end = (start + len).min(self.input.len());
+        let char_start = self.input[..start].chars().count();
+        let char_len = self.input[start..end].chars().count();
+        ParseError {
+            message: message.into(),
+            input: self.input.to_string(),
+            start: char_start,
+            len: char_len.max(1),
+        }
+    }
+
+    /// Create an error pointing at the next character
+    pub(crate) fn error_here(&self, message: impl Into<String>) -> ParseError {
+        let len = self.peek().map_or(0, char::len_utf8);
+        self.error(message, self.pos, len)
+    }
+
+    /// Fail unless the next character is `expected`
+    pub(crate) fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.next();
+                Ok(())
+            }
+            Some(c) => Err(self.error_here(format!("expected `{expected}`, found `{c}`"))),
+            None => Err(self.error_here(format!("expected `{expected}`, found end of input"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cursor;
+
+    #[test]
+    fn test_error_display() {
+        let cursor = Cursor::new("föo >= 1.0 bar");
+        let err = cursor.error("unexpected input", 12, 3);
+        assert_eq!(err.start, 11);
+        assert_eq!(err.len, 3);
+        assert_eq!(
+            err.to_string(),
+            "unexpected input\nföo >= 1.0 bar\n           ^^^"
+        );
+    }
+}
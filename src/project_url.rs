@@ -0,0 +1,156 @@
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Metadata;
+
+/// Aliases of the well-known labels of [PEP 753](https://peps.python.org/pep-0753/), normalized
+const LABEL_ALIASES: &[(&str, &str)] = &[
+    ("repository", "source"),
+    ("sourcecode", "source"),
+    ("github", "source"),
+    ("changes", "changelog"),
+    ("whatsnew", "changelog"),
+    ("history", "changelog"),
+    ("docs", "documentation"),
+    ("bugs", "issues"),
+    ("issue", "issues"),
+    ("tracker", "issues"),
+    ("issuetracker", "issues"),
+    ("bugtracker", "issues"),
+    ("sponsor", "funding"),
+    ("donate", "funding"),
+    ("donation", "funding"),
+];
+
+/// Normalize a label like PEP 753 and resolve aliases of well-known labels
+///
+/// `Bug Tracker` and `issues` both normalize to `issues`.
+fn normalize_label(label: &str) -> String {
+    let label: String = label
+        .chars()
+        .filter(|c| !c.is_ascii_punctuation() && !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    match LABEL_ALIASES.iter().find(|(alias, _)| *alias == label) {
+        Some((_, well_known)) => well_known.to_string(),
+        None => label,
+    }
+}
+
+/// A labeled URL from a `Project-URL` field
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProjectUrl {
+    /// The label, as written.
+    pub label: String,
+    /// The URL.
+    pub url: String,
+}
+
+impl ProjectUrl {
+    /// Parse a `label, url` field value, `None` if it has no label
+    pub fn parse(value: &str) -> Option<Self> {
+        let (label, url) = value.split_once(',')?;
+        let (label, url) = (label.trim(), url.trim());
+        if label.is_empty() || url.is_empty() {
+            return None;
+        }
+        Some(Self {
+            label: label.to_string(),
+            url: url.to_string(),
+        })
+    }
+
+    /// Returns the PEP 753 normalized label with aliases of well-known labels resolved,
+    /// e.g. `source` for `Source Code` and `Repository`
+    pub fn normalized_label(&self) -> String {
+        normalize_label(&self.label)
+    }
+}
+
+impl fmt::Display for ProjectUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, {}", self.label, self.url)
+    }
+}
+
+impl Metadata {
+    /// Parse [`Metadata::project_urls`], malformed entries are skipped
+    pub fn parsed_project_urls(&self) -> Vec<ProjectUrl> {
+        self.project_urls
+            .iter()
+            .filter_map(|value| ProjectUrl::parse(value))
+            .collect()
+    }
+
+    /// Look up a project URL by its normalized label, e.g. `homepage`, `source`,
+    /// `issues`, `changelog` or `documentation`
+    ///
+    /// Falls back to `Home-Page` for `homepage` and `Download-URL` for `download`.
+    pub fn project_url(&self, label: &str) -> Option<&str> {
+        let label = normalize_label(label);
+        let project_url = self.project_urls.iter().find_map(|value| {
+            let (url_label, url) = value.split_once(',')?;
+            let url = url.trim();
+            (normalize_label(url_label) == label && !url.is_empty()).then_some(url)
+        });
+        project_url.or(match label.as_str() {
+            "homepage" => self.home_page.as_deref(),
+            "download" => self.download_url.as_deref(),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProjectUrl;
+    use crate::Metadata;
+
+    #[test]
+    fn test_parse_project_url() {
+        let project_url = ProjectUrl::parse("Bug Tracker, https://example.com/issues").unwrap();
+        assert_eq!(project_url.label, "Bug Tracker");
+        assert_eq!(project_url.url, "https://example.com/issues");
+        assert_eq!(project_url.normalized_label(), "issues");
+        assert_eq!(
+            project_url.to_string(),
+            "Bug Tracker, https://example.com/issues"
+        );
+        assert_eq!(
+            ProjectUrl::parse("What's New?, https://example.com")
+                .unwrap()
+                .normalized_label(),
+            "changelog"
+        );
+        assert!(ProjectUrl::parse("https://example.com").is_none());
+        assert!(ProjectUrl::parse(", https://example.com").is_none());
+    }
+
+    #[test]
+    fn test_project_url_lookup() {
+        let s = "Metadata-Version: 2.1\nName: asdf\nVersion: 1.0\nHome-page: https://example.com\nDownload-URL: https://example.com/download\nProject-URL: Source Code, https://github.com/example/asdf\nProject-URL: Docs, https://asdf.readthedocs.io\nProject-URL: invalid";
+        let meta: Metadata = s.parse().unwrap();
+        assert_eq!(meta.parsed_project_urls().len(), 2);
+        assert_eq!(
+            meta.project_url("source"),
+            Some("https://github.com/example/asdf")
+        );
+        assert_eq!(
+            meta.project_url("Documentation"),
+            Some("https://asdf.readthedocs.io")
+        );
+        assert_eq!(meta.project_url("Home-Page"), Some("https://example.com"));
+        assert_eq!(
+            meta.project_url("download"),
+            Some("https://example.com/download")
+        );
+        assert_eq!(meta.project_url("issues"), None);
+
+        let s = "Metadata-Version: 2.1\nName: asdf\nVersion: 1.0\nHome-page: https://example.com\nProject-URL: Homepage, https://example.org";
+        let meta: Metadata = s.parse().unwrap();
+        assert_eq!(meta.project_url("homepage"), Some("https://example.org"));
+    }
+}
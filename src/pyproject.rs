@@ -0,0 +1,387 @@
+use std::path::Path;
+
+use toml::{Table, Value};
+
+use crate::name;
+use crate::{Error, MarkerTree, Metadata, Requirement};
+
+/// Core metadata fields corresponding to the `[project]` keys that may be dynamic
+///
+/// `name` has no entry, it can't be dynamic. A dynamic `version` is listed as `Version`,
+/// only the build backend knows the value.
+const DYNAMIC_FIELDS: &[(&str, &[&str])] = &[
+    ("version", &["Version"]),
+    ("description", &["Summary"]),
+    ("readme", &["Description", "Description-Content-Type"]),
+    ("requires-python", &["Requires-Python"]),
+    ("license", &["License", "License-Expression"]),
+    ("license-files", &["License-File"]),
+    ("authors", &["Author", "Author-email"]),
+    ("maintainers", &["Maintainer", "Maintainer-email"]),
+    ("keywords", &["Keywords"]),
+    ("classifiers", &["Classifier"]),
+    ("urls", &["Project-URL"]),
+    ("dependencies", &["Requires-Dist"]),
+    (
+        "optional-dependencies",
+        &["Provides-Extra", "Requires-Dist"],
+    ),
+];
+
+fn invalid(message: impl Into<String>) -> Error {
+    Error::InvalidPyproject(message.into())
+}
+
+fn get_str<'a>(table: &'a Table, key: &str, name: &str) -> Result<Option<&'a str>, Error> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(Value::String(value)) => Ok(Some(value)),
+        Some(_) => Err(invalid(format!("`{name}` must be a string"))),
+    }
+}
+
+fn get_str_array(table: &Table, key: &str, name: &str) -> Result<Vec<String>, Error> {
+    let error = || invalid(format!("`{name}` must be an array of strings"));
+    match table.get(key) {
+        None => Ok(Vec::new()),
+        Some(Value::Array(values)) => values
+            .iter()
+            .map(|value| value.as_str().map(ToString::to_string).ok_or_else(error))
+            .collect(),
+        Some(_) => Err(error()),
+    }
+}
+
+fn get_table<'a>(table: &'a Table, key: &str, name: &str) -> Result<Option<&'a Table>, Error> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(Value::Table(value)) => Ok(Some(value)),
+        Some(_) => Err(invalid(format!("`{name}` must be a table"))),
+    }
+}
+
+/// Read a file referenced by `pyproject.toml`, `None` without a project directory
+fn read_file(root: Option<&Path>, file: &str) -> Result<Option<String>, Error> {
+    root.map(|root| fs_err::read_to_string(root.join(file)))
+        .transpose()
+        .map_err(Error::from)
+}
+
+/// Convert `authors` or `maintainers` into the name and email fields
+///
+/// Entries with an email become `name <email>` in the email field, others the name field.
+fn convert_people(project: &Table, key: &str) -> Result<(Option<String>, Option<String>), Error> {
+    let name = format!("project.{key}");
+    let people = match project.get(key) {
+        None => return Ok((None, None)),
+        Some(Value::Array(people)) => people,
+        Some(_) => return Err(invalid(format!("`{name}` must be an array of tables"))),
+    };
+    let mut names = Vec::new();
+    let mut emails = Vec::new();
+    for person in people {
+        let person = person
+            .as_table()
+            .ok_or_else(|| invalid(format!("`{name}` must be an array of tables")))?;
+        let person_name = get_str(person, "name", &format!("{name}.name"))?;
+        let email = get_str(person, "email", &format!("{name}.email"))?;
+        match (person_name, email) {
+            (Some(person_name), Some(email)) => emails.push(format!("{person_name} <{email}>")),
+            (None, Some(email)) => emails.push(email.to_string()),
+            (Some(person_name), None) => names.push(person_name.to_string()),
+            (None, None) => {}
+        }
+    }
+    let join = |values: Vec<String>| (!values.is_empty()).then(|| values.join(", "));
+    Ok((join(names), join(emails)))
+}
+
+/// Add an `extra == "{extra}"` condition to the marker of a requirement
+fn add_extra_marker(requirement: &str, extra: &str) -> Result<String, Error> {
+    let mut requirement: Requirement = requirement.parse()?;
+    requirement.add_marker(MarkerTree::extra(extra));
+    Ok(requirement.to_string())
+}
+
+fn content_type_for(file: &str) -> &'static str {
+    let file = file.to_ascii_lowercase();
+    if file.ends_with(".md") {
+        "text/markdown"
+    } else if file.ends_with(".rst") {
+        "text/x-rst"
+    } else {
+        "text/plain"
+    }
+}
+
+impl Metadata {
+    /// Convert the [PEP 621](https://peps.python.org/pep-0621/) `[project]` table of a
+    /// `pyproject.toml` into metadata
+    ///
+    /// Files referenced by `readme` and `license` aren't read, use
+    /// [`Metadata::from_pyproject_path`] to resolve them. A dynamic `version`, e.g. from
+    /// setuptools-scm, is left empty and listed in [`Metadata::dynamic`]. Extra names are
+    /// normalized following [PEP 685](https://peps.python.org/pep-0685/).
+    pub fn from_pyproject_toml(content: &str) -> Result<Self, Error> {
+        Self::from_pyproject(content, None)
+    }
+
+    /// Read a `pyproject.toml` file like [`Metadata::from_pyproject_toml`], resolving
+    /// `readme` and `license` files relative to its directory
+    pub fn from_pyproject_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let content = fs_err::read_to_string(path)?;
+        Self::from_pyproject(&content, Some(path.parent().unwrap_or(Path::new(""))))
+    }
+
+    fn from_pyproject(content: &str, root: Option<&Path>) -> Result<Self, Error> {
+        let pyproject: Table = content.parse()?;
+        let project = get_table(&pyproject, "project", "project")?
+            .ok_or_else(|| invalid("missing `[project]` table"))?;
+        let dynamic = get_str_array(project, "dynamic", "project.dynamic")?;
+        let is_dynamic = |key: &str| dynamic.iter().any(|d| d == key);
+
+        let name = get_str(project, "name", "project.name")?
+            .ok_or_else(|| invalid("missing `project.name`"))?;
+        let version = match get_str(project, "version", "project.version")? {
+            Some(version) => version.to_string(),
+            None if is_dynamic("version") => String::new(),
+            None => return Err(invalid("missing `project.version`")),
+        };
+
+        let mut metadata = Metadata {
+            name: name.to_string(),
+            version,
+            summary: get_str(project, "description", "project.description")?
+                .map(ToString::to_string),
+            requires_python: get_str(project, "requires-python", "project.requires-python")?
+                .map(ToString::to_string),
+            classifiers: get_str_array(project, "classifiers", "project.classifiers")?,
+            requires_dist: get_str_array(project, "dependencies", "project.dependencies")?,
+            license_files: get_str_array(project, "license-files", "project.license-files")?,
+            ..Metadata::default()
+        };
+
+        match project.get("readme") {
+            None => {}
+            Some(Value::String(file)) => {
+                metadata.description = read_file(root, file)?;
+                metadata.description_content_type = Some(content_type_for(file).to_string());
+            }
+            Some(Value::Table(readme)) => {
+                let content_type = get_str(readme, "content-type", "project.readme.content-type")?;
+                let file = get_str(readme, "file", "project.readme.file")?;
+                metadata.description = match (get_str(readme, "text", "project.readme.text")?, file)
+                {
+                    (Some(text), None) => Some(text.to_string()),
+                    (None, Some(file)) => read_file(root, file)?,
+                    _ => return Err(invalid("`project.readme` needs either `file` or `text`")),
+                };
+                metadata.description_content_type = content_type
+                    .or(file.map(content_type_for))
+                    .map(ToString::to_string);
+            }
+            Some(_) => return Err(invalid("`project.readme` must be a string or a table")),
+        }
+
+        match project.get("license") {
+            None => {}
+            // PEP 639 SPDX license expression
+            Some(Value::String(expression)) => {
+                metadata.license_expression = Some(expression.clone());
+            }
+            Some(Value::Table(license)) => {
+                metadata.license = match (
+                    get_str(license, "text", "project.license.text")?,
+                    get_str(license, "file", "project.license.file")?,
+                ) {
+                    (Some(text), None) => Some(text.to_string()),
+                    (None, Some(file)) => read_file(root, file)?,
+                    _ => return Err(invalid("`project.license` needs either `file` or `text`")),
+                };
+            }
+            Some(_) => return Err(invalid("`project.license` must be a string or a table")),
+        }
+
+        (metadata.author, metadata.author_email) = convert_people(project, "authors")?;
+        (metadata.maintainer, metadata.maintainer_email) = convert_people(project, "maintainers")?;
+
+        let keywords = get_str_array(project, "keywords", "project.keywords")?;
+        if !keywords.is_empty() {
+            metadata.keywords = Some(keywords.join(","));
+        }
+
+        if let Some(urls) = get_table(project, "urls", "project.urls")? {
+            for (label, url) in urls {
+                let url = url
+                    .as_str()
+                    .ok_or_else(|| invalid(format!("`project.urls.{label}` must be a string")))?;
+                metadata.project_urls.push(format!("{label}, {url}"));
+            }
+        }
+
+        if let Some(optional_dependencies) = get_table(
+            project,
+            "optional-dependencies",
+            "project.optional-dependencies",
+        )? {
+            for extra in optional_dependencies.keys() {
+                let requirements = get_str_array(
+                    optional_dependencies,
+                    extra,
+                    &format!("project.optional-dependencies.{extra}"),
+                )?;
+                metadata.provides_extras.push(name::normalize(extra));
+                for requirement in requirements {
+                    metadata
+                        .requires_dist
+                        .push(add_extra_marker(&requirement, extra)?);
+                }
+            }
+        }
+
+        for key in &dynamic {
+            if let Some((_, fields)) = DYNAMIC_FIELDS.iter().find(|(name, _)| name == key) {
+                for field in *fields {
+                    if !metadata.dynamic.iter().any(|d| d == field) {
+                        metadata.dynamic.push(field.to_string());
+                    }
+                }
+            }
+        }
+
+        // Use the oldest metadata version that supports all fields
+        metadata.metadata_version =
+            if metadata.license_expression.is_some() || !metadata.license_files.is_empty() {
+                "2.4"
+            } else if !metadata.dynamic.is_empty() {
+                "2.2"
+            } else {
+                "2.1"
+            }
+            .to_string();
+        Ok(metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Metadata};
+
+    #[test]
+    fn test_from_pyproject_toml() {
+        let content = r##"
+[build-system]
+requires = ["setuptools"]
+
+[project]
+name = "spam-eggs"
+version = "2020.0.0"
+description = "Lovely Spam! Wonderful Spam!"
+readme = { text = "# Spam", content-type = "text/markdown" }
+requires-python = ">=3.8"
+license = { text = "MIT" }
+keywords = ["egg", "bacon", "sausage"]
+authors = [
+  { name = "Pradyun Gedam", email = "pradyun@example.com" },
+  { name = "Tzu-Ping Chung" },
+  { email = "hi@pradyun.com" },
+]
+classifiers = ["Programming Language :: Python"]
+dependencies = ["httpx", "gidgethub[httpx]>4.0.0"]
+dynamic = ["classifiers"]
+
+[project.optional-dependencies]
+gui = ["PyQt5"]
+cli = ["rich; python_version >= '3.9' or os_name == 'nt'", "click"]
+"Dev_Tools" = ["pytest"]
+
+[project.urls]
+Homepage = "https://example.com"
+"Bug Tracker" = "https://github.com/me/spam/issues"
+"##;
+        let meta = Metadata::from_pyproject_toml(content).unwrap();
+        assert_eq!(meta.metadata_version, "2.2");
+        assert_eq!(meta.name, "spam-eggs");
+        assert_eq!(meta.version, "2020.0.0");
+        assert_eq!(
+            meta.summary.as_deref(),
+            Some("Lovely Spam! Wonderful Spam!")
+        );
+        assert_eq!(meta.description.as_deref(), Some("# Spam"));
+        assert_eq!(
+            meta.description_content_type.as_deref(),
+            Some("text/markdown")
+        );
+        assert_eq!(meta.requires_python.as_deref(), Some(">=3.8"));
+        assert_eq!(meta.license.as_deref(), Some("MIT"));
+        assert_eq!(meta.keywords.as_deref(), Some("egg,bacon,sausage"));
+        assert_eq!(meta.author.as_deref(), Some("Tzu-Ping Chung"));
+        assert_eq!(
+            meta.author_email.as_deref(),
+            Some("Pradyun Gedam <pradyun@example.com>, hi@pradyun.com")
+        );
+        // Tables keep their declared order
+        assert_eq!(meta.provides_extras, ["gui", "cli", "dev-tools"]);
+        assert_eq!(
+            meta.requires_dist,
+            [
+                "httpx",
+                "gidgethub[httpx]>4.0.0",
+                "PyQt5; extra == \"gui\"",
+                "rich; (python_version >= \"3.9\" or os_name == \"nt\") and extra == \"cli\"",
+                "click; extra == \"cli\"",
+                "pytest; extra == \"dev-tools\"",
+            ]
+        );
+        assert_eq!(
+            meta.project_urls,
+            [
+                "Homepage, https://example.com",
+                "Bug Tracker, https://github.com/me/spam/issues"
+            ]
+        );
+        assert_eq!(meta.dynamic, ["Classifier"]);
+    }
+
+    #[test]
+    fn test_from_pyproject_toml_dynamic_version() {
+        let content = "[project]\nname = \"spam\"\ndynamic = [\"version\"]";
+        let meta = Metadata::from_pyproject_toml(content).unwrap();
+        assert_eq!(meta.name, "spam");
+        assert_eq!(meta.version, "");
+        assert_eq!(meta.dynamic, ["Version"]);
+        assert_eq!(meta.metadata_version, "2.2");
+
+        let content =
+            "[project]\nname = \"spam\"\nversion = \"1.0\"\nlicense = \"MIT OR Apache-2.0\"";
+        let meta = Metadata::from_pyproject_toml(content).unwrap();
+        assert!(meta.dynamic.is_empty());
+        assert_eq!(meta.metadata_version, "2.4");
+        assert_eq!(
+            meta.license_expression.as_deref(),
+            Some("MIT OR Apache-2.0")
+        );
+    }
+
+    #[test]
+    fn test_from_pyproject_toml_invalid() {
+        for content in [
+            "[tool.foo]",
+            "[project]\nversion = \"1.0\"",
+            "[project]\nname = \"spam\"",
+            "[project]\nname = 1\nversion = \"1.0\"",
+            "[project]\nname = \"spam\"\nversion = \"1.0\"\ndependencies = \"httpx\"",
+        ] {
+            let result = Metadata::from_pyproject_toml(content);
+            assert!(
+                matches!(result, Err(Error::InvalidPyproject(_))),
+                "{content}"
+            );
+        }
+        assert!(matches!(
+            Metadata::from_pyproject_toml("[project"),
+            Err(Error::Toml(_))
+        ));
+    }
+}
@@ -0,0 +1,289 @@
+use std::collections::HashSet;
+use std::io::{self, Read, Seek};
+use std::str::FromStr;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use zip::result::ZipError;
+use zip::ZipArchive;
+
+use crate::hash::Hasher;
+use crate::Error;
+
+/// A single row of a wheel's `RECORD` file
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordEntry {
+    /// Path of the file relative to the archive root.
+    pub path: String,
+    /// Hash of the file in the form `{algorithm}={urlsafe b64 digest without padding}`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hash: Option<String>,
+    /// Size of the file in bytes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub size: Option<u64>,
+}
+
+/// Parsed contents of the `.dist-info/RECORD` file of a wheel
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Record {
+    /// The files listed in the record, in order of appearance.
+    pub entries: Vec<RecordEntry>,
+}
+
+/// A file whose contents don't match its `RECORD` entry
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordMismatch {
+    /// The digest of the file differs from the recorded one
+    Hash {
+        /// Path of the file
+        path: String,
+        /// The hash listed in `RECORD`
+        expected: String,
+        /// The hash computed from the archive, using the same algorithm
+        actual: String,
+    },
+    /// The size of the file differs from the recorded one
+    Size {
+        /// Path of the file
+        path: String,
+        /// The size listed in `RECORD`
+        expected: u64,
+        /// The size of the file in the archive
+        actual: u64,
+    },
+    /// The recorded hash uses an unknown or insecure algorithm such as `md5`
+    UnsupportedHash {
+        /// Path of the file
+        path: String,
+        /// The hash listed in `RECORD`
+        hash: String,
+    },
+    /// The file has no recorded hash
+    NoHash {
+        /// Path of the file
+        path: String,
+    },
+}
+
+/// Result of verifying a wheel against its `RECORD` file
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecordVerification {
+    /// Files listed in `RECORD` but missing from the archive.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub missing: Vec<String>,
+    /// Files in the archive that are not listed in `RECORD`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub extra: Vec<String>,
+    /// Files whose hash or size doesn't match `RECORD`, a file with both wrong is listed twice.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub mismatched: Vec<RecordMismatch>,
+}
+
+impl RecordVerification {
+    /// Returns `true` if every file matches `RECORD`
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty() && self.mismatched.is_empty()
+    }
+}
+
+impl Record {
+    /// Parse the contents of a `RECORD` file
+    pub fn parse(content: &[u8]) -> Result<Self, Error> {
+        let content = String::from_utf8_lossy(content);
+        let mut entries = Vec::new();
+        for (index, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let invalid = |reason: &str| Error::InvalidRecord {
+                line: index + 1,
+                reason: reason.to_string(),
+            };
+            let fields = parse_csv_row(line).ok_or_else(|| invalid("unterminated quote"))?;
+            let (path, hash, size) = match fields.as_slice() {
+                [path] => (path, "", ""),
+                [path, hash] => (path, hash.as_str(), ""),
+                [path, hash, size] => (path, hash.as_str(), size.as_str()),
+                _ => return Err(invalid("expected at most 3 columns")),
+            };
+            if path.is_empty() {
+                return Err(invalid("empty path"));
+            }
+            let size = if size.is_empty() {
+                None
+            } else {
+                Some(size.parse().map_err(|_| invalid("invalid size"))?)
+            };
+            entries.push(RecordEntry {
+                path: path.to_string(),
+                hash: (!hash.is_empty()).then(|| hash.to_string()),
+                size,
+            });
+        }
+        Ok(Self { entries })
+    }
+
+    /// Verify the files of the wheel archive in `reader` against this record
+    ///
+    /// Every file is streamed through the hash algorithm listed for it,
+    /// so this reads the whole archive.
+    pub fn verify<R: Read + Seek>(&self, reader: R) -> Result<RecordVerification, Error> {
+        self.verify_archive(&mut ZipArchive::new(reader)?)
+    }
+
+    /// Verify the files of an open wheel archive against this record
+    pub(crate) fn verify_archive<R: Read + Seek>(
+        &self,
+        archive: &mut ZipArchive<R>,
+    ) -> Result<RecordVerification, Error> {
+        let mut result = RecordVerification::default();
+
+        let mut recorded = HashSet::new();
+        for entry in &self.entries {
+            recorded.insert(entry.path.as_str());
+            let mut file = match archive.by_name(&entry.path) {
+                Ok(file) => file,
+                Err(ZipError::FileNotFound) => {
+                    result.missing.push(entry.path.clone());
+                    continue;
+                }
+                Err(err) => return Err(err.into()),
+            };
+            let hash = match &entry.hash {
+                Some(hash) => hash,
+                // `RECORD` can't contain its own hash
+                None if entry.path.ends_with(".dist-info/RECORD") => continue,
+                None => {
+                    result.mismatched.push(RecordMismatch::NoHash {
+                        path: entry.path.clone(),
+                    });
+                    continue;
+                }
+            };
+            let hasher = hash
+                .split_once('=')
+                .and_then(|(algorithm, digest)| Some((algorithm, digest, Hasher::new(algorithm)?)));
+            let Some((algorithm, expected_digest, mut hasher)) = hasher else {
+                result.mismatched.push(RecordMismatch::UnsupportedHash {
+                    path: entry.path.clone(),
+                    hash: hash.clone(),
+                });
+                continue;
+            };
+            let size = io::copy(&mut file, &mut hasher)?;
+            let actual_digest = URL_SAFE_NO_PAD.encode(hasher.finalize());
+            if actual_digest != expected_digest.trim_end_matches('=') {
+                result.mismatched.push(RecordMismatch::Hash {
+                    path: entry.path.clone(),
+                    expected: hash.clone(),
+                    actual: format!("{algorithm}={actual_digest}"),
+                });
+            }
+            if let Some(expected) = entry.size.filter(|&expected| expected != size) {
+                result.mismatched.push(RecordMismatch::Size {
+                    path: entry.path.clone(),
+                    expected,
+                    actual: size,
+                });
+            }
+        }
+
+        result.extra = archive
+            .file_names()
+            .filter(|name| {
+                !name.ends_with('/')
+                    && !recorded.contains(name)
+                    // Signatures of `RECORD` are not listed in it
+                    && !name.ends_with(".dist-info/RECORD.jws")
+                    && !name.ends_with(".dist-info/RECORD.p7s")
+            })
+            .map(ToString::to_string)
+            .collect();
+        result.extra.sort();
+        Ok(result)
+    }
+}
+
+impl FromStr for Record {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Record::parse(s.as_bytes())
+    }
+}
+
+/// Split a CSV row as written by Python's `csv` module, returns `None` on an unterminated quote
+fn parse_csv_row(line: &str) -> Option<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut in_quotes = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            }
+            '"' if field.is_empty() => in_quotes = true,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    if in_quotes {
+        return None;
+    }
+    fields.push(field);
+    Some(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_csv_row, Record, RecordEntry};
+    use crate::Error;
+
+    #[test]
+    fn test_parse_csv_row() {
+        assert_eq!(parse_csv_row("a,b,c").unwrap(), ["a", "b", "c"]);
+        assert_eq!(parse_csv_row("a,,").unwrap(), ["a", "", ""]);
+        assert_eq!(
+            parse_csv_row(r#""a,b",sha256=x,1"#).unwrap(),
+            ["a,b", "sha256=x", "1"]
+        );
+        assert_eq!(parse_csv_row(r#""a""b",,"#).unwrap(), [r#"a"b"#, "", ""]);
+        assert!(parse_csv_row(r#""a,b,c"#).is_none());
+    }
+
+    #[test]
+    fn test_parse_record() {
+        let s = "build/__init__.py,sha256=zSpWXQTTyTYmubHTCbqRXxO9B3J0jtSPQa1kXR33bzc,14550\r\nbuild-0.4.0.dist-info/RECORD,,\r\n";
+        let record: Record = s.parse().unwrap();
+        assert_eq!(
+            record.entries,
+            [
+                RecordEntry {
+                    path: "build/__init__.py".to_string(),
+                    hash: Some("sha256=zSpWXQTTyTYmubHTCbqRXxO9B3J0jtSPQa1kXR33bzc".to_string()),
+                    size: Some(14550),
+                },
+                RecordEntry {
+                    path: "build-0.4.0.dist-info/RECORD".to_string(),
+                    hash: None,
+                    size: None,
+                },
+            ]
+        );
+
+        let record: Result<Record, Error> = "foo.py,sha256=abc,large".parse();
+        assert!(matches!(record, Err(Error::InvalidRecord { line: 1, .. })));
+    }
+}
@@ -0,0 +1,248 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::parser::{Cursor, ParseError};
+use crate::{Error, MarkerEnvironment, MarkerTree, VersionSpecifiers};
+
+/// The version constraint of a requirement
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum VersionOrUrl {
+    /// Version specifiers, e.g. `>=1.0,<2`
+    VersionSpecifier(VersionSpecifiers),
+    /// A direct reference, e.g. `https://example.com/foo-1.0.tar.gz`
+    Url(String),
+}
+
+/// A [PEP 508](https://peps.python.org/pep-0508/) dependency specification
+///
+/// `name[extra1,extra2]>=1.0,<2; python_version >= "3.8"`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Requirement {
+    /// The name of the required distribution, as written.
+    pub name: String,
+    /// The requested extras.
+    pub extras: Vec<String>,
+    /// The version specifiers or direct URL, if any.
+    pub version_or_url: Option<VersionOrUrl>,
+    /// The environment marker, if any.
+    pub marker: Option<MarkerTree>,
+}
+
+impl fmt::Display for Requirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if !self.extras.is_empty() {
+            write!(f, "[{}]", self.extras.join(","))?;
+        }
+        match &self.version_or_url {
+            Some(VersionOrUrl::VersionSpecifier(specifiers)) => write!(f, "{specifiers}")?,
+            Some(VersionOrUrl::Url(url)) => {
+                write!(f, " @ {url}")?;
+                // A space is required to tell the marker apart from the URL
+                if self.marker.is_some() {
+                    write!(f, " ")?;
+                }
+            }
+            None => {}
+        }
+        if let Some(marker) = &self.marker {
+            write!(f, "; {marker}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Requirement {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Requirement::parse(s).map_err(Error::InvalidRequirement)
+    }
+}
+
+impl Requirement {
+    /// Returns `true` if the requirement applies to `env` with the requested `extras`
+    ///
+    /// Requirements without a marker always apply.
+    pub fn evaluate_markers(&self, env: &MarkerEnvironment, extras: &[&str]) -> bool {
+        match &self.marker {
+            Some(marker) => marker.evaluate(env, extras),
+            None => true,
+        }
+    }
+
+    /// Restrict the requirement to environments where `marker` also holds
+    pub(crate) fn add_marker(&mut self, marker: MarkerTree) {
+        self.marker = Some(match self.marker.take() {
+            Some(existing) => existing.and(marker),
+            None => marker,
+        });
+    }
+
+    /// Parse a PEP 508 requirement, pointing at the offending span on failure
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
+        let mut cursor = Cursor::new(s);
+        cursor.eat_whitespace();
+        let name = parse_identifier(&mut cursor, "distribution name")?;
+
+        cursor.eat_whitespace();
+        let mut extras = Vec::new();
+        if cursor.eat("[") {
+            cursor.eat_whitespace();
+            if !cursor.eat("]") {
+                loop {
+                    cursor.eat_whitespace();
+                    extras.push(parse_identifier(&mut cursor, "extra name")?);
+                    cursor.eat_whitespace();
+                    if cursor.eat("]") {
+                        break;
+                    }
+                    cursor.expect(',')?;
+                }
+            }
+        }
+
+        cursor.eat_whitespace();
+        let version_or_url = match cursor.peek() {
+            Some('@') => {
+                cursor.next();
+                cursor.eat_whitespace();
+                let start = cursor.pos();
+                let url = cursor.take_while(|c| !c.is_whitespace());
+                if url.is_empty() {
+                    return Err(cursor.error_here("expected a URL"));
+                }
+                if !url.contains(':') {
+                    return Err(cursor.error("expected a URL with a scheme", start, url.len()));
+                }
+                Some(VersionOrUrl::Url(url.to_string()))
+            }
+            Some('(') => {
+                cursor.next();
+                let specifiers = VersionSpecifiers::parse(&mut cursor)?;
+                cursor.eat_whitespace();
+                cursor.expect(')')?;
+                Some(VersionOrUrl::VersionSpecifier(specifiers))
+            }
+            Some('<' | '>' | '=' | '!' | '~') => Some(VersionOrUrl::VersionSpecifier(
+                VersionSpecifiers::parse(&mut cursor)?,
+            )),
+            _ => None,
+        };
+
+        cursor.eat_whitespace();
+        let marker = if cursor.eat(";") {
+            let marker = MarkerTree::parse(&mut cursor)?;
+            cursor.eat_whitespace();
+            Some(marker)
+        } else {
+            None
+        };
+
+        if !cursor.at_end() {
+            let message = if marker.is_some() {
+                "expected `and`, `or` or end of input"
+            } else if version_or_url.is_some() {
+                "expected `,`, `;` or end of input"
+            } else {
+                "expected `[`, `(`, `@`, a version specifier, `;` or end of input"
+            };
+            return Err(cursor.error(message, cursor.pos(), cursor.rest().len()));
+        }
+
+        Ok(Self {
+            name,
+            extras,
+            version_or_url,
+            marker,
+        })
+    }
+}
+
+/// Parse a name or extra: `[A-Za-z0-9]([-_.A-Za-z0-9]*[A-Za-z0-9])?`
+fn parse_identifier(cursor: &mut Cursor, what: &str) -> Result<String, ParseError> {
+    let start = cursor.pos();
+    match cursor.peek() {
+        Some(c) if c.is_ascii_alphanumeric() => {}
+        Some(_) => return Err(cursor.error_here(format!("expected a {what}"))),
+        None => return Err(cursor.error_here(format!("expected a {what}, found end of input"))),
+    }
+    let identifier = cursor.take_while(|c| c.is_ascii_alphanumeric() || "-_.".contains(c));
+    if !identifier.ends_with(|c: char| c.is_ascii_alphanumeric()) {
+        return Err(cursor.error(
+            format!("{what} must end with a letter or digit"),
+            start,
+            identifier.len(),
+        ));
+    }
+    Ok(identifier.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Requirement, VersionOrUrl};
+    use crate::{Error, MarkerTree};
+
+    #[test]
+    fn test_parse_requirement() {
+        let req: Requirement =
+            "requests [security , tests] >= 2.8.1, == 2.8.* ; python_version < \"2.7\""
+                .parse()
+                .unwrap();
+        assert_eq!(req.name, "requests");
+        assert_eq!(req.extras, ["security", "tests"]);
+        assert!(matches!(
+            &req.version_or_url,
+            Some(VersionOrUrl::VersionSpecifier(specifiers)) if specifiers.to_string() == ">=2.8.1,==2.8.*"
+        ));
+        assert!(matches!(req.marker, Some(MarkerTree::Expression(_))));
+        assert_eq!(
+            req.to_string(),
+            "requests[security,tests]>=2.8.1,==2.8.*; python_version < \"2.7\""
+        );
+
+        let req: Requirement = "pip @ https://github.com/pypa/pip/archive/1.3.1.zip#sha1=da9234ee9982d4bbb3c72346a6de940a148ea686 ; extra == 'test'"
+            .parse()
+            .unwrap();
+        assert!(matches!(
+            &req.version_or_url,
+            Some(VersionOrUrl::Url(url)) if url.starts_with("https://github.com")
+        ));
+        assert_eq!(
+            req.to_string(),
+            "pip @ https://github.com/pypa/pip/archive/1.3.1.zip#sha1=da9234ee9982d4bbb3c72346a6de940a148ea686 ; extra == \"test\""
+        );
+
+        let req: Requirement = "zope.interface (>=3.6.0)".parse().unwrap();
+        assert_eq!(req.name, "zope.interface");
+        assert_eq!(req.to_string(), "zope.interface>=3.6.0");
+
+        let req: Requirement = "name".parse().unwrap();
+        assert_eq!(req.to_string(), "name");
+        let req: Requirement = "name[]".parse().unwrap();
+        assert!(req.extras.is_empty());
+    }
+
+    #[test]
+    fn test_parse_invalid_requirement() {
+        for (input, start, len) in [
+            ("", 0, 1),
+            ("-foo", 0, 1),
+            ("foo-", 0, 4),
+            ("foo[bar", 7, 1),
+            ("foo[bar baz]", 8, 1),
+            ("foo 1.0", 4, 3),
+            ("foo >= 1.0 bar", 11, 3),
+            ("foo (>=1.0", 10, 1),
+            ("foo @ ", 6, 1),
+            ("foo @ example", 6, 7),
+            ("foo; os_name == 'nt' bar", 21, 3),
+            ("foo; bar == 'nt'", 5, 3),
+        ] {
+            let err = Requirement::parse(input).unwrap_err();
+            assert_eq!((err.start, err.len), (start, len), "{input}: {err}");
+        }
+        let req: Result<Requirement, Error> = "foo >= 1.0 bar".parse();
+        assert!(matches!(req, Err(Error::InvalidRequirement(_))));
+    }
+}
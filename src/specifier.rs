@@ -0,0 +1,438 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::parser::{Cursor, ParseError};
+use crate::{Error, Version};
+
+/// Comparison operator of a version specifier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operator {
+    /// `==`
+    Equal,
+    /// `==` with a trailing `.*`
+    EqualStar,
+    /// `!=`
+    NotEqual,
+    /// `!=` with a trailing `.*`
+    NotEqualStar,
+    /// `~=`
+    TildeEqual,
+    /// `<`
+    LessThan,
+    /// `<=`
+    LessThanEqual,
+    /// `>`
+    GreaterThan,
+    /// `>=`
+    GreaterThanEqual,
+    /// `===`
+    ExactEqual,
+}
+
+impl Operator {
+    /// Parse an operator at the cursor, longest match first
+    pub(crate) fn parse(cursor: &mut Cursor) -> Option<Self> {
+        const OPERATORS: &[(&str, Operator)] = &[
+            ("===", Operator::ExactEqual),
+            ("==", Operator::Equal),
+            ("!=", Operator::NotEqual),
+            ("~=", Operator::TildeEqual),
+            ("<=", Operator::LessThanEqual),
+            (">=", Operator::GreaterThanEqual),
+            ("<", Operator::LessThan),
+            (">", Operator::GreaterThan),
+        ];
+        OPERATORS
+            .iter()
+            .find(|(s, _)| cursor.eat(s))
+            .map(|&(_, operator)| operator)
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operator = match self {
+            Operator::Equal | Operator::EqualStar => "==",
+            Operator::NotEqual | Operator::NotEqualStar => "!=",
+            Operator::TildeEqual => "~=",
+            Operator::LessThan => "<",
+            Operator::LessThanEqual => "<=",
+            Operator::GreaterThan => ">",
+            Operator::GreaterThanEqual => ">=",
+            Operator::ExactEqual => "===",
+        };
+        write!(f, "{operator}")
+    }
+}
+
+/// A single version specifier, e.g. `>=3.8` or `==3.9.*`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VersionSpecifier {
+    /// The comparison operator.
+    pub operator: Operator,
+    /// The version to compare against, without the `.*` suffix of wildcard operators.
+    ///
+    /// For `===` this is the default version if the string isn't a valid version.
+    pub version: Version,
+    /// The string `===` compares against as written, `None` for the other operators.
+    pub arbitrary: Option<String>,
+}
+
+impl fmt::Display for VersionSpecifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(arbitrary) = &self.arbitrary {
+            return write!(f, "{}{}", self.operator, arbitrary);
+        }
+        write!(f, "{}{}", self.operator, self.version)?;
+        if matches!(self.operator, Operator::EqualStar | Operator::NotEqualStar) {
+            write!(f, ".*")?;
+        }
+        Ok(())
+    }
+}
+
+impl VersionSpecifier {
+    /// Returns `true` if `version` satisfies this specifier
+    ///
+    /// Pre-releases are not excluded, see [`Version::is_prerelease`] to filter them.
+    pub fn contains(&self, version: &Version) -> bool {
+        let spec = &self.version;
+        match self.operator {
+            Operator::Equal => {
+                // A specifier without a local label matches any local version
+                if spec.is_local() {
+                    version == spec
+                } else {
+                    &without_local(version) == spec
+                }
+            }
+            Operator::NotEqual => !Self {
+                operator: Operator::Equal,
+                version: spec.clone(),
+                arbitrary: None,
+            }
+            .contains(version),
+            Operator::EqualStar => is_prefix_match(spec, version),
+            Operator::NotEqualStar => !is_prefix_match(spec, version),
+            Operator::TildeEqual => {
+                // `~=2.2.post3` is `>=2.2.post3, ==2.*`
+                let mut prefix = Version::from_release(&spec.release[..spec.release.len() - 1]);
+                prefix.epoch = spec.epoch;
+                &without_local(version) >= spec && is_prefix_match(&prefix, version)
+            }
+            Operator::LessThan => {
+                let version = without_local(version);
+                // `<3.8` must not match pre-releases of 3.8 unless the specifier is one itself
+                version < *spec
+                    && (spec.is_prerelease()
+                        || !version.is_prerelease()
+                        || base_version(&version) != base_version(spec))
+            }
+            Operator::LessThanEqual => &without_local(version) <= spec,
+            Operator::GreaterThan => {
+                // `>3.8` must not match post-releases or local versions of 3.8
+                without_local(version) > *spec
+                    && (spec.post.is_some()
+                        || version.post.is_none()
+                        || base_version(version) != base_version(spec))
+                    && (!version.is_local() || base_version(version) != base_version(spec))
+            }
+            Operator::GreaterThanEqual => &without_local(version) >= spec,
+            // Arbitrary equality compares strings, case-insensitively like `packaging`
+            Operator::ExactEqual => match &self.arbitrary {
+                Some(arbitrary) => version.to_string().eq_ignore_ascii_case(arbitrary),
+                None => version.to_string() == spec.to_string(),
+            },
+        }
+    }
+
+    /// Parse a single specifier at the cursor
+    pub(crate) fn parse(cursor: &mut Cursor) -> Result<Self, ParseError> {
+        let start = cursor.pos();
+        let mut operator = Operator::parse(cursor)
+            .ok_or_else(|| cursor.error_here("expected a version comparison operator"))?;
+        cursor.eat_whitespace();
+        let version_start = cursor.pos();
+        if operator == Operator::ExactEqual {
+            // `===` takes any string that isn't whitespace or a delimiter, e.g. `===foobar`
+            let arbitrary = cursor.take_while(|c| !c.is_whitespace() && !",;)".contains(c));
+            if arbitrary.is_empty() {
+                return Err(cursor.error_here("expected a version"));
+            }
+            return Ok(Self {
+                operator,
+                version: arbitrary.parse().unwrap_or_default(),
+                arbitrary: Some(arbitrary.to_string()),
+            });
+        }
+        let raw_version = cursor.take_while(|c| c.is_ascii_alphanumeric() || "-_.*+!".contains(c));
+        if raw_version.is_empty() {
+            return Err(cursor.error_here("expected a version"));
+        }
+        let version_error =
+            |message: String| cursor.error(message, version_start, raw_version.len());
+        let (version, wildcard) = match raw_version.strip_suffix(".*") {
+            Some(version) => (version, true),
+            None => (raw_version, false),
+        };
+        let version: Version = version
+            .parse()
+            .map_err(|err: Error| version_error(err.to_string()))?;
+        if wildcard {
+            operator = match operator {
+                Operator::Equal => Operator::EqualStar,
+                Operator::NotEqual => Operator::NotEqualStar,
+                _ => {
+                    return Err(cursor.error(
+                        format!("`{operator}` can't be used with a wildcard version"),
+                        start,
+                        cursor.pos() - start,
+                    ))
+                }
+            };
+            if version.is_local() {
+                return Err(version_error(
+                    "wildcard versions can't have a local version label".to_string(),
+                ));
+            }
+        }
+        if operator == Operator::TildeEqual && version.release.len() < 2 {
+            return Err(version_error(
+                "`~=` requires at least two release segments".to_string(),
+            ));
+        }
+        if version.is_local() && !matches!(operator, Operator::Equal | Operator::NotEqual) {
+            return Err(version_error(format!(
+                "`{operator}` can't be used with a local version"
+            )));
+        }
+        Ok(Self {
+            operator,
+            version,
+            arbitrary: None,
+        })
+    }
+}
+
+fn without_local(version: &Version) -> Version {
+    Version {
+        local: Vec::new(),
+        ..version.clone()
+    }
+}
+
+fn base_version(version: &Version) -> Version {
+    Version {
+        epoch: version.epoch,
+        ..Version::from_release(version.release.clone())
+    }
+}
+
+/// Match `version` against the wildcard specifier `prefix.*`
+fn is_prefix_match(prefix: &Version, version: &Version) -> bool {
+    if prefix.epoch != version.epoch {
+        return false;
+    }
+    // Compare the release segments zero-padded to the length of the prefix
+    let release_matches = prefix
+        .release
+        .iter()
+        .enumerate()
+        .all(|(i, n)| version.release.get(i).copied().unwrap_or(0) == *n);
+    if !release_matches {
+        return false;
+    }
+    // Any suffix of the prefix itself, e.g. `==1.0rc1.*`, has to match exactly
+    (prefix.pre.is_none() || prefix.pre == version.pre)
+        && (prefix.post.is_none() || prefix.post == version.post)
+        && (prefix.dev.is_none() || prefix.dev == version.dev)
+}
+
+/// A comma separated list of version specifiers, e.g. `>=3.8,!=3.9.*`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct VersionSpecifiers(pub Vec<VersionSpecifier>);
+
+impl VersionSpecifiers {
+    /// Parse specifiers at the cursor until something that can't start a specifier
+    pub(crate) fn parse(cursor: &mut Cursor) -> Result<Self, ParseError> {
+        let mut specifiers = Vec::new();
+        loop {
+            cursor.eat_whitespace();
+            specifiers.push(VersionSpecifier::parse(cursor)?);
+            cursor.eat_whitespace();
+            if !cursor.eat(",") {
+                break;
+            }
+        }
+        Ok(Self(specifiers))
+    }
+
+    /// Returns `true` if `version` satisfies all specifiers
+    pub fn contains(&self, version: &Version) -> bool {
+        self.0.iter().all(|specifier| specifier.contains(version))
+    }
+
+    /// Returns `true` if there are no specifiers, which allows any version
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the specifiers
+    pub fn iter(&self) -> std::slice::Iter<'_, VersionSpecifier> {
+        self.0.iter()
+    }
+}
+
+impl FromStr for VersionSpecifiers {
+    type Err = Error;
+
+    /// Parse a complete list of specifiers, an empty string allows any version
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        let mut cursor = Cursor::new(s);
+        let specifiers = Self::parse(&mut cursor).map_err(Error::InvalidSpecifier)?;
+        if !cursor.at_end() {
+            return Err(Error::InvalidSpecifier(cursor.error(
+                "expected `,` or end of input",
+                cursor.pos(),
+                cursor.rest().len(),
+            )));
+        }
+        Ok(specifiers)
+    }
+}
+
+impl fmt::Display for VersionSpecifiers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let specifiers: Vec<String> = self.0.iter().map(ToString::to_string).collect();
+        write!(f, "{}", specifiers.join(","))
+    }
+}
+
+impl<'a> IntoIterator for &'a VersionSpecifiers {
+    type Item = &'a VersionSpecifier;
+    type IntoIter = std::slice::Iter<'a, VersionSpecifier>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Operator, VersionSpecifiers};
+    use crate::parser::Cursor;
+    use crate::Error;
+
+    fn parse(s: &str) -> Result<VersionSpecifiers, crate::parser::ParseError> {
+        let mut cursor = Cursor::new(s);
+        let specifiers = VersionSpecifiers::parse(&mut cursor)?;
+        assert!(cursor.at_end(), "{s}");
+        Ok(specifiers)
+    }
+
+    #[test]
+    fn test_parse_specifiers() {
+        let specifiers = parse(">= 3.8, !=3.9.*,<4,~=3.8.1").unwrap();
+        let operators: Vec<_> = specifiers.iter().map(|s| s.operator).collect();
+        assert_eq!(
+            operators,
+            [
+                Operator::GreaterThanEqual,
+                Operator::NotEqualStar,
+                Operator::LessThan,
+                Operator::TildeEqual
+            ]
+        );
+        assert_eq!(specifiers.to_string(), ">=3.8,!=3.9.*,<4,~=3.8.1");
+
+        let specifiers = parse("===1.0+local").unwrap();
+        assert_eq!(specifiers.to_string(), "===1.0+local");
+        let specifiers = parse("===foobar, >=1").unwrap();
+        assert_eq!(specifiers.0[0].arbitrary.as_deref(), Some("foobar"));
+        assert_eq!(specifiers.to_string(), "===foobar,>=1");
+    }
+
+    #[test]
+    fn test_contains() {
+        for (specifier, version, expected) in [
+            ("==1.0", "1.0.0", true),
+            ("==1.0", "1.0+local", true),
+            ("==1.0+local", "1.0", false),
+            ("!=1.0", "1.0.1", true),
+            ("==3.9.*", "3.9.7", true),
+            ("==3.9.*", "3.10", false),
+            ("==3.*", "3", true),
+            ("!=3.9.*", "3.9.0", false),
+            ("~=2.2", "2.3", true),
+            ("~=2.2", "3.0", false),
+            ("~=1.4.5", "1.4.9", true),
+            ("~=1.4.5", "1.5.0", false),
+            ("~=1.4.5", "1.4.4", false),
+            ("<3.8", "3.7.9", true),
+            ("<3.8", "3.8.0rc1", false),
+            ("<3.8rc2", "3.8.0rc1", true),
+            ("<=3.8", "3.8+local", true),
+            (">3.8", "3.8.1", true),
+            (">3.8", "3.8.post1", false),
+            (">3.8", "3.8+local", false),
+            (">3.8.post1", "3.8.post2", true),
+            (">=3.8", "3.10", true),
+            (">=3.8", "3.8rc1", false),
+            ("===1.0", "1.0", true),
+            ("===1.0", "1.0.0", false),
+            ("===1.0RC1", "1.0rc1", true),
+            ("===1.0-rc1", "1.0rc1", false),
+            ("===foobar", "1.0", false),
+        ] {
+            let specifiers = parse(specifier).unwrap();
+            let version = version.parse().unwrap();
+            assert_eq!(
+                specifiers.0[0].contains(&version),
+                expected,
+                "{specifier} contains {version}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_specifiers_contains() {
+        let specifiers: VersionSpecifiers = ">=3.8, !=3.9.*".parse().unwrap();
+        for (version, expected) in [
+            ("3.7", false),
+            ("3.8", true),
+            ("3.9.1", false),
+            ("3.10", true),
+        ] {
+            assert_eq!(
+                specifiers.contains(&version.parse().unwrap()),
+                expected,
+                "{version}"
+            );
+        }
+        let any: VersionSpecifiers = " ".parse().unwrap();
+        assert!(any.is_empty());
+        assert!(any.contains(&"2.7".parse().unwrap()));
+
+        let err = ">=3.8 <4".parse::<VersionSpecifiers>().unwrap_err();
+        assert!(matches!(err, Error::InvalidSpecifier(err) if err.start == 6));
+    }
+
+    #[test]
+    fn test_parse_invalid_specifiers() {
+        let err = parse(">=3.8,3.9").unwrap_err();
+        assert_eq!(err.start, 6);
+        let err = parse(">=3.*").unwrap_err();
+        assert_eq!((err.start, err.len), (0, 5));
+        let err = parse("~=3").unwrap_err();
+        assert_eq!((err.start, err.len), (2, 1));
+        let err = parse(">=3.8+local").unwrap_err();
+        assert_eq!((err.start, err.len), (2, 9));
+        let err = parse("==foo").unwrap_err();
+        assert_eq!((err.start, err.len), (2, 3));
+        let err = parse(">=").unwrap_err();
+        assert_eq!((err.start, err.len), (2, 1));
+    }
+}
@@ -0,0 +1,368 @@
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::metadata::field_introduced_in;
+use crate::name;
+use crate::{
+    Error, Metadata, MetadataVersion, ParseWarning, Requirement, Version, VersionSpecifiers,
+};
+
+/// Fields that may be listed in `Dynamic`
+const DYNAMIC_FIELDS: &[&str] = &[
+    "Platform",
+    "Supported-Platform",
+    "Summary",
+    "Description",
+    "Description-Content-Type",
+    "Keywords",
+    "Home-page",
+    "Download-URL",
+    "Author",
+    "Author-email",
+    "Maintainer",
+    "Maintainer-email",
+    "License",
+    "License-Expression",
+    "License-File",
+    "Classifier",
+    "Requires-Dist",
+    "Requires-Python",
+    "Requires-External",
+    "Project-URL",
+    "Provides-Extra",
+    "Provides-Dist",
+    "Obsoletes-Dist",
+];
+
+/// Severity of a [`Diagnostic`]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The metadata is usable but likely not what the author intended.
+    Warning,
+    /// The metadata violates the core metadata specification.
+    Error,
+}
+
+/// A problem found by [`Metadata::validate`]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+    /// How severe the problem is.
+    pub severity: Severity,
+    /// The name of the offending field, e.g. `Requires-Dist`.
+    pub field: String,
+    /// Description of the problem.
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn error(field: &str, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            field: field.to_string(),
+            message: message.into(),
+        }
+    }
+
+    fn warning(field: &str, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            field: field.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{severity}: {}: {}", self.field, self.message)
+    }
+}
+
+impl Metadata {
+    /// Check the metadata against the core metadata specification of its `Metadata-Version`
+    ///
+    /// Checks required fields, that fields are allowed by the `Metadata-Version`
+    /// and the syntax of names, versions, requirements and specifiers.
+    /// Returns an empty list if no problems were found.
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        let metadata_version = self.parsed_metadata_version();
+        if let MetadataVersion::Unknown(version) = &metadata_version {
+            // Newer minor versions are backwards compatible
+            let diagnostic = if metadata_version.is_supported() {
+                Diagnostic::warning
+            } else {
+                Diagnostic::error
+            };
+            diagnostics.push(diagnostic(
+                "Metadata-Version",
+                format!("unknown metadata version `{version}`"),
+            ));
+        }
+
+        if self.name.is_empty() {
+            diagnostics.push(Diagnostic::error("Name", "missing required field"));
+        } else if !name::is_valid(&self.name) {
+            diagnostics.push(Diagnostic::error(
+                "Name",
+                format!("invalid distribution name `{}`", self.name),
+            ));
+        }
+        if self.version.is_empty() {
+            diagnostics.push(Diagnostic::error("Version", "missing required field"));
+        } else if let Err(err) = self.version.parse::<Version>() {
+            // Legacy versions were allowed before PEP 440 was required by 1.2
+            let diagnostic = if matches!(
+                metadata_version,
+                MetadataVersion::V1_0 | MetadataVersion::V1_1
+            ) {
+                Diagnostic::warning
+            } else {
+                Diagnostic::error
+            };
+            diagnostics.push(diagnostic("Version", err.to_string()));
+        }
+
+        // Fields added after 1.0
+        let introduced = [
+            ("Supported-Platform", !self.supported_platforms.is_empty()),
+            ("Download-URL", self.download_url.is_some()),
+            ("Classifier", !self.classifiers.is_empty()),
+            ("Maintainer", self.maintainer.is_some()),
+            ("Maintainer-email", self.maintainer_email.is_some()),
+            ("Requires-Dist", !self.requires_dist.is_empty()),
+            ("Provides-Dist", !self.provides_dist.is_empty()),
+            ("Obsoletes-Dist", !self.obsoletes_dist.is_empty()),
+            ("Requires-Python", self.requires_python.is_some()),
+            ("Requires-External", !self.requires_external.is_empty()),
+            ("Project-URL", !self.project_urls.is_empty()),
+            (
+                "Description-Content-Type",
+                self.description_content_type.is_some(),
+            ),
+            ("Provides-Extra", !self.provides_extras.is_empty()),
+            ("Dynamic", !self.dynamic.is_empty()),
+            ("License-Expression", self.license_expression.is_some()),
+            ("License-File", !self.license_files.is_empty()),
+        ];
+        if metadata_version.major_minor().is_some() {
+            for (field, present) in introduced {
+                let since = field_introduced_in(field);
+                if present && metadata_version < since {
+                    diagnostics.push(Diagnostic::error(
+                        field,
+                        format!("requires Metadata-Version {since} or later"),
+                    ));
+                }
+            }
+        }
+
+        if self.summary.as_deref().is_some_and(|s| s.contains('\n')) {
+            diagnostics.push(Diagnostic::error("Summary", "must be a single line"));
+        }
+        if self.description.is_none() {
+            diagnostics.push(Diagnostic::warning("Description", "missing description"));
+        }
+        if let Some(content_type) = &self.description_content_type {
+            let mime_type = content_type.split(';').next().unwrap_or_default().trim();
+            if !["text/plain", "text/x-rst", "text/markdown"]
+                .iter()
+                .any(|known| known.eq_ignore_ascii_case(mime_type))
+            {
+                diagnostics.push(Diagnostic::warning(
+                    "Description-Content-Type",
+                    format!("unknown content type `{mime_type}`"),
+                ));
+            }
+        }
+
+        for (field, values) in [
+            ("Requires-Dist", &self.requires_dist),
+            ("Provides-Dist", &self.provides_dist),
+            ("Obsoletes-Dist", &self.obsoletes_dist),
+        ] {
+            for value in values {
+                if let Err(err) = Requirement::parse(value) {
+                    diagnostics.push(Diagnostic::error(field, err.message));
+                }
+            }
+        }
+        if let Some(requires_python) = &self.requires_python {
+            if let Err(err) = requires_python.parse::<VersionSpecifiers>() {
+                let message = match err {
+                    Error::InvalidSpecifier(err) => err.message,
+                    err => err.to_string(),
+                };
+                diagnostics.push(Diagnostic::error("Requires-Python", message));
+            }
+        }
+        for extra in &self.provides_extras {
+            if !name::is_valid(extra) {
+                diagnostics.push(Diagnostic::error(
+                    "Provides-Extra",
+                    format!("invalid extra name `{extra}`"),
+                ));
+            }
+        }
+        for project_url in &self.project_urls {
+            match project_url.split_once(',') {
+                Some((label, url)) if !label.trim().is_empty() && !url.trim().is_empty() => {
+                    if label.trim().chars().count() > 32 {
+                        diagnostics.push(Diagnostic::error(
+                            "Project-URL",
+                            format!("label `{}` is longer than 32 characters", label.trim()),
+                        ));
+                    }
+                }
+                _ => diagnostics.push(Diagnostic::error(
+                    "Project-URL",
+                    format!("expected `label, url`, found `{project_url}`"),
+                )),
+            }
+        }
+        for field in &self.dynamic {
+            if !DYNAMIC_FIELDS
+                .iter()
+                .any(|known| known.eq_ignore_ascii_case(field))
+            {
+                diagnostics.push(Diagnostic::error(
+                    "Dynamic",
+                    format!("`{field}` can't be dynamic"),
+                ));
+            }
+        }
+
+        diagnostics
+    }
+
+    /// Parse distribution metadata, rejecting metadata that doesn't comply with the specification
+    ///
+    /// Like the specification requires, metadata with a newer major `Metadata-Version`
+    /// is rejected with [`Error::UnsupportedMetadataVersion`]. In addition to
+    /// [`Metadata::validate`], single-use fields must not be repeated.
+    /// Returns [`Error::InvalidMetadata`] with all errors found, warnings are ignored.
+    pub fn parse_strict(content: &[u8]) -> Result<Self, Error> {
+        let (metadata, warnings) = Self::parse_with_diagnostics(content)?;
+        let metadata_version = metadata.parsed_metadata_version();
+        if metadata_version.major_minor().is_some() && !metadata_version.is_supported() {
+            return Err(Error::UnsupportedMetadataVersion(
+                metadata.metadata_version.clone(),
+            ));
+        }
+        let mut errors: Vec<Diagnostic> = warnings
+            .iter()
+            .filter_map(|warning| match warning {
+                ParseWarning::DuplicateField(field) => {
+                    Some(Diagnostic::error(field, "may only be specified once"))
+                }
+                _ => None,
+            })
+            .collect();
+        errors.extend(
+            metadata
+                .validate()
+                .into_iter()
+                .filter(|diagnostic| diagnostic.severity == Severity::Error),
+        );
+        if errors.is_empty() {
+            Ok(metadata)
+        } else {
+            Err(Error::InvalidMetadata(errors))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{Diagnostic, Severity};
+    use crate::{Error, Metadata};
+
+    fn validate(s: &str) -> Vec<Diagnostic> {
+        let meta: Metadata = s.parse().unwrap();
+        meta.validate()
+    }
+
+    #[test]
+    fn test_validate_valid() {
+        let diagnostics = validate(
+            "Metadata-Version: 2.4\nName: asdf\nVersion: 1.0\nSummary: a Python package\nLicense-Expression: MIT\nLicense-File: LICENSE\nRequires-Dist: packaging>=19.0\nRequires-Python: >=3.8\nProject-URL: Homepage, https://example.com\nProvides-Extra: test\nDynamic: Classifier\n\nA Python package",
+        );
+        assert_eq!(diagnostics, []);
+    }
+
+    #[test]
+    fn test_validate_invalid() {
+        let diagnostics = validate(
+            "Metadata-Version: 2.1\nName: -asdf\nVersion: 2004d\nLicense-Expression: MIT\nDynamic: Name\nRequires-Dist: packaging >= \nRequires-Python: 3.8\nProject-URL: https://example.com\nDescription-Content-Type: text/html",
+        );
+        let fields: Vec<_> = diagnostics
+            .iter()
+            .map(|d| (d.severity, d.field.as_str()))
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                (Severity::Error, "Name"),
+                (Severity::Error, "Version"),
+                (Severity::Error, "Dynamic"),
+                (Severity::Error, "License-Expression"),
+                (Severity::Warning, "Description"),
+                (Severity::Warning, "Description-Content-Type"),
+                (Severity::Error, "Requires-Dist"),
+                (Severity::Error, "Requires-Python"),
+                (Severity::Error, "Project-URL"),
+                (Severity::Error, "Dynamic"),
+            ]
+        );
+        assert_eq!(
+            diagnostics[3].to_string(),
+            "error: License-Expression: requires Metadata-Version 2.4 or later"
+        );
+
+        let diagnostics = validate("Metadata-Version: 1.0\nName: asdf\nVersion: 2004d\n\nasdf");
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        // Parsing rejects newer major versions, but the fields can be set directly
+        let meta = Metadata {
+            metadata_version: "3.0".to_string(),
+            ..Metadata::from_str("Metadata-Version: 2.1\nName: asdf\nVersion: 1.0\n\nasdf").unwrap()
+        };
+        let diagnostics = meta.validate();
+        assert_eq!(diagnostics[0].field, "Metadata-Version");
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        let diagnostics = validate("Metadata-Version: 2.5\nName: asdf\nVersion: 1.0\n\nasdf");
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_parse_strict() {
+        let s = "Metadata-Version: 2.1\nName: asdf\nVersion: 1.0";
+        assert!(Metadata::parse_strict(s.as_bytes()).is_ok());
+
+        let s = "Metadata-Version: 2.1\nName: asdf\nVersion: 1.0\nSummary: one\nsummary: two";
+        let err = Metadata::parse_strict(s.as_bytes()).unwrap_err();
+        match err {
+            Error::InvalidMetadata(errors) => {
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].field, "Summary");
+            }
+            err => panic!("unexpected error {err}"),
+        }
+
+        let s = "Metadata-Version: 3.0\nName: asdf\nVersion: 1.0";
+        assert!(matches!(
+            Metadata::parse_strict(s.as_bytes()),
+            Err(Error::UnsupportedMetadataVersion(version)) if version == "3.0"
+        ));
+    }
+}
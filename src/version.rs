@@ -0,0 +1,510 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Error;
+
+/// Kind of a pre-release version segment
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PreReleaseKind {
+    /// `a`, also spelled `alpha`
+    Alpha,
+    /// `b`, also spelled `beta`
+    Beta,
+    /// `rc`, also spelled `c`, `pre` or `preview`
+    Rc,
+}
+
+impl fmt::Display for PreReleaseKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PreReleaseKind::Alpha => write!(f, "a"),
+            PreReleaseKind::Beta => write!(f, "b"),
+            PreReleaseKind::Rc => write!(f, "rc"),
+        }
+    }
+}
+
+/// Pre-release segment of a version, e.g. `rc1`
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PreRelease {
+    /// The pre-release phase.
+    pub kind: PreReleaseKind,
+    /// The pre-release number, `0` if omitted.
+    pub number: u64,
+}
+
+/// Segment of a local version label, e.g. `ubuntu` and `1` in `+ubuntu.1`
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LocalSegment {
+    /// A lowercase alphanumeric segment
+    String(String),
+    /// A purely numeric segment
+    Number(u64),
+}
+
+impl fmt::Display for LocalSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LocalSegment::String(s) => write!(f, "{s}"),
+            LocalSegment::Number(n) => write!(f, "{n}"),
+        }
+    }
+}
+
+impl PartialOrd for LocalSegment {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for LocalSegment {
+    /// Numeric segments sort after alphanumeric ones, which sort lexicographically
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (LocalSegment::Number(a), LocalSegment::Number(b)) => a.cmp(b),
+            (LocalSegment::String(a), LocalSegment::String(b)) => a.cmp(b),
+            (LocalSegment::Number(_), LocalSegment::String(_)) => Ordering::Greater,
+            (LocalSegment::String(_), LocalSegment::Number(_)) => Ordering::Less,
+        }
+    }
+}
+
+/// A [PEP 440](https://peps.python.org/pep-0440/) version
+///
+/// Parsing accepts all the alternative spellings PEP 440 allows and normalizes them,
+/// so `1.0-RC.1` and `1.0rc1` compare equal and both display as `1.0rc1`.
+/// Comparison follows PEP 440 semantics, trailing zeros in the release segment are
+/// insignificant: `1.0 == 1.0.0`.
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, Default)]
+pub struct Version {
+    /// The epoch, `0` if omitted.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub epoch: u64,
+    /// The release segment, e.g. `[1, 2, 3]` for `1.2.3`.
+    pub release: Vec<u64>,
+    /// The pre-release segment.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pre: Option<PreRelease>,
+    /// The post-release number.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub post: Option<u64>,
+    /// The development release number.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dev: Option<u64>,
+    /// The local version label, empty if there is none.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub local: Vec<LocalSegment>,
+}
+
+impl Version {
+    /// Create a final release version from its release segment, e.g. `[3, 9]`
+    pub fn from_release(release: impl Into<Vec<u64>>) -> Self {
+        Self {
+            release: release.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Returns `true` for pre-releases and development releases
+    pub fn is_prerelease(&self) -> bool {
+        self.pre.is_some() || self.dev.is_some()
+    }
+
+    /// Returns `true` if the version has a local version label
+    pub fn is_local(&self) -> bool {
+        !self.local.is_empty()
+    }
+
+    /// Returns the release segment without trailing zeros
+    fn release_trimmed(&self) -> &[u64] {
+        let len = self
+            .release
+            .iter()
+            .rposition(|&n| n != 0)
+            .map_or(0, |pos| pos + 1);
+        &self.release[..len]
+    }
+
+    /// Sort key of the pre, post and dev segments as defined by `packaging`
+    fn suffix_key(&self) -> (PreKey, Option<u64>, DevKey) {
+        let pre = match self.pre {
+            // A bare development release sorts before all pre-releases
+            None if self.post.is_none() && self.dev.is_some() => PreKey::BeforeAll,
+            None => PreKey::Final,
+            Some(pre) => PreKey::Pre(pre),
+        };
+        let dev = match self.dev {
+            None => DevKey::Final,
+            Some(dev) => DevKey::Dev(dev),
+        };
+        (pre, self.post, dev)
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum PreKey {
+    BeforeAll,
+    Pre(PreRelease),
+    Final,
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum DevKey {
+    Dev(u64),
+    Final,
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl Hash for Version {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.epoch.hash(state);
+        self.release_trimmed().hash(state);
+        self.pre.hash(state);
+        self.post.hash(state);
+        self.dev.hash(state);
+        self.local.hash(state);
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.epoch
+            .cmp(&other.epoch)
+            .then_with(|| self.release_trimmed().cmp(other.release_trimmed()))
+            .then_with(|| self.suffix_key().cmp(&other.suffix_key()))
+            .then_with(|| self.local.cmp(&other.local))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.epoch != 0 {
+            write!(f, "{}!", self.epoch)?;
+        }
+        let release: Vec<String> = self.release.iter().map(ToString::to_string).collect();
+        write!(f, "{}", release.join("."))?;
+        if let Some(pre) = self.pre {
+            write!(f, "{}{}", pre.kind, pre.number)?;
+        }
+        if let Some(post) = self.post {
+            write!(f, ".post{post}")?;
+        }
+        if let Some(dev) = self.dev {
+            write!(f, ".dev{dev}")?;
+        }
+        if !self.local.is_empty() {
+            let local: Vec<String> = self.local.iter().map(ToString::to_string).collect();
+            write!(f, "+{}", local.join("."))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Version {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lowercase = s.trim().to_ascii_lowercase();
+        let mut parser = Parser {
+            input: &lowercase,
+            pos: 0,
+        };
+        parser
+            .parse_version()
+            .map_err(|reason| Error::InvalidVersion {
+                version: s.to_string(),
+                reason: reason.to_string(),
+            })
+    }
+}
+
+/// Cursor over a lowercased version string
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn rest(&self) -> &str {
+        &self.input[self.pos..]
+    }
+
+    fn eat(&mut self, prefix: &str) -> bool {
+        if self.rest().starts_with(prefix) {
+            self.pos += prefix.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consume one of the optional `-`, `_` or `.` separators
+    fn eat_separator(&mut self) -> bool {
+        self.eat("-") || self.eat("_") || self.eat(".")
+    }
+
+    fn number(&mut self) -> Option<Result<u64, &'static str>> {
+        let len = self
+            .rest()
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.rest().len());
+        if len == 0 {
+            return None;
+        }
+        let number = self.rest()[..len]
+            .parse()
+            .map_err(|_| "number is too large");
+        self.pos += len;
+        Some(number)
+    }
+
+    /// Try to parse `[sep]label[sep][number]` for one of `labels`, backtracking on failure
+    fn labeled_number<T: Copy>(
+        &mut self,
+        labels: &[(&str, T)],
+    ) -> Result<Option<(T, Option<u64>)>, &'static str> {
+        let start = self.pos;
+        self.eat_separator();
+        let Some(&(_, value)) = labels.iter().find(|(label, _)| self.eat(label)) else {
+            self.pos = start;
+            return Ok(None);
+        };
+        let before_separator = self.pos;
+        self.eat_separator();
+        match self.number() {
+            Some(number) => Ok(Some((value, Some(number?)))),
+            None => {
+                self.pos = before_separator;
+                Ok(Some((value, None)))
+            }
+        }
+    }
+
+    fn parse_version(&mut self) -> Result<Version, &'static str> {
+        self.eat("v");
+        let mut version = Version::default();
+
+        let first = self.number().ok_or("expected a release number")??;
+        if self.eat("!") {
+            version.epoch = first;
+            version.release.push(
+                self.number()
+                    .ok_or("expected a release number after the epoch")??,
+            );
+        } else {
+            version.release.push(first);
+        }
+        loop {
+            let start = self.pos;
+            if !self.eat(".") {
+                break;
+            }
+            match self.number() {
+                Some(number) => version.release.push(number?),
+                None => {
+                    self.pos = start;
+                    break;
+                }
+            }
+        }
+
+        // Longer spellings first so that `alpha` isn't consumed as `a`
+        const PRE: &[(&str, PreReleaseKind)] = &[
+            ("alpha", PreReleaseKind::Alpha),
+            ("beta", PreReleaseKind::Beta),
+            ("preview", PreReleaseKind::Rc),
+            ("pre", PreReleaseKind::Rc),
+            ("rc", PreReleaseKind::Rc),
+            ("a", PreReleaseKind::Alpha),
+            ("b", PreReleaseKind::Beta),
+            ("c", PreReleaseKind::Rc),
+        ];
+        if let Some((kind, number)) = self.labeled_number(PRE)? {
+            version.pre = Some(PreRelease {
+                kind,
+                number: number.unwrap_or(0),
+            });
+        }
+
+        // The implicit post-release spelling `1.0-1`
+        let start = self.pos;
+        if self.eat("-") {
+            match self.number() {
+                Some(number) => version.post = Some(number?),
+                None => self.pos = start,
+            }
+        }
+        if version.post.is_none() {
+            if let Some(((), number)) =
+                self.labeled_number(&[("post", ()), ("rev", ()), ("r", ())])?
+            {
+                version.post = Some(number.unwrap_or(0));
+            }
+        }
+
+        if let Some(((), number)) = self.labeled_number(&[("dev", ())])? {
+            version.dev = Some(number.unwrap_or(0));
+        }
+
+        if self.eat("+") {
+            for segment in self.rest().split(['-', '_', '.']) {
+                if segment.is_empty() || !segment.chars().all(|c| c.is_ascii_alphanumeric()) {
+                    return Err("invalid local version label");
+                }
+                version.local.push(match segment.parse() {
+                    Ok(number) if segment.chars().all(|c| c.is_ascii_digit()) => {
+                        LocalSegment::Number(number)
+                    }
+                    _ => LocalSegment::String(segment.to_string()),
+                });
+            }
+            self.pos = self.input.len();
+        }
+
+        if !self.rest().is_empty() {
+            return Err("unexpected trailing characters");
+        }
+        Ok(version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LocalSegment, PreRelease, PreReleaseKind, Version};
+    use crate::Error;
+
+    #[test]
+    fn test_parse_version() {
+        let version: Version = "1!2.3.4rc5.post6.dev7+ubuntu.8".parse().unwrap();
+        assert_eq!(version.epoch, 1);
+        assert_eq!(version.release, [2, 3, 4]);
+        assert_eq!(
+            version.pre,
+            Some(PreRelease {
+                kind: PreReleaseKind::Rc,
+                number: 5
+            })
+        );
+        assert_eq!(version.post, Some(6));
+        assert_eq!(version.dev, Some(7));
+        assert_eq!(
+            version.local,
+            [
+                LocalSegment::String("ubuntu".to_string()),
+                LocalSegment::Number(8)
+            ]
+        );
+        assert!(version.is_prerelease());
+        assert!(version.is_local());
+    }
+
+    #[test]
+    fn test_normalize_version() {
+        for (input, normalized) in [
+            ("1.0", "1.0"),
+            ("v1.0", "1.0"),
+            (" 1.0\n", "1.0"),
+            ("0!1.0", "1.0"),
+            ("1.0-alpha.1", "1.0a1"),
+            ("1.0.BETA2", "1.0b2"),
+            ("1.0c1", "1.0rc1"),
+            ("1.0-preview_3", "1.0rc3"),
+            ("1.0a", "1.0a0"),
+            ("1.0-1", "1.0.post1"),
+            ("1.0-r2", "1.0.post2"),
+            ("1.0rev", "1.0.post0"),
+            ("1.0.post", "1.0.post0"),
+            ("1.0-dev", "1.0.dev0"),
+            ("1.0_dev_3", "1.0.dev3"),
+            ("1.0+Ubuntu-1_2", "1.0+ubuntu.1.2"),
+            ("1.01.002", "1.1.2"),
+        ] {
+            let version: Version = input.parse().unwrap();
+            assert_eq!(version.to_string(), normalized, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_invalid_version() {
+        for input in [
+            "", "v", "1.", "1.0.", "1.0a1a", "1.0+", "1.0+a..b", "1.0+a!", "foo", "1.0 foo", "1!",
+            "1.0-",
+        ] {
+            let result: Result<Version, Error> = input.parse();
+            assert!(
+                matches!(result, Err(Error::InvalidVersion { .. })),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_version_ordering() {
+        // Examples from PEP 440, in ascending order
+        let versions = [
+            "1.0.dev456",
+            "1.0a1",
+            "1.0a2.dev456",
+            "1.0a12.dev456",
+            "1.0a12",
+            "1.0b1.dev456",
+            "1.0b2",
+            "1.0b2.post345.dev456",
+            "1.0b2.post345",
+            "1.0rc1.dev456",
+            "1.0rc1",
+            "1.0",
+            "1.0+abc.5",
+            "1.0+abc.7",
+            "1.0+5",
+            "1.0.post456.dev34",
+            "1.0.post456",
+            "1.0.15",
+            "1.1.dev1",
+            "1!0.1",
+        ];
+        let versions: Vec<Version> = versions.iter().map(|v| v.parse().unwrap()).collect();
+        for pair in versions.windows(2) {
+            assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+        }
+
+        let a: Version = "1.0".parse().unwrap();
+        let b: Version = "1.0.0".parse().unwrap();
+        assert_eq!(a, b);
+        assert_eq!(Version::from_release([1, 0]), a);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let version: Version = "1!2.0rc1.post3.dev4+ubuntu.1".parse().unwrap();
+        let json = serde_json::to_string(&version).unwrap();
+        let deserialized: Version = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.to_string(), version.to_string());
+
+        let version: Version = serde_json::from_str(r#"{"release": [3, 9]}"#).unwrap();
+        assert_eq!(version, Version::from_release([3, 9]));
+    }
+}
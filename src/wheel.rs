@@ -0,0 +1,290 @@
+use std::fmt;
+use std::str::FromStr;
+
+use mailparse::MailHeaderMap;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, PackageName};
+
+/// Optional build tag of a wheel filename
+///
+/// Acts as a tie-breaker if two wheels have the same version,
+/// it must start with a number and wheels are sorted by that number first.
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BuildTag {
+    /// The leading number of the build tag.
+    pub number: u64,
+    /// The remainder of the build tag after the leading number, may be empty.
+    pub suffix: String,
+}
+
+impl fmt::Display for BuildTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.number, self.suffix)
+    }
+}
+
+impl BuildTag {
+    fn parse(s: &str) -> Option<Self> {
+        let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        let (number, suffix) = s.split_at(split);
+        Some(Self {
+            number: number.parse().ok()?,
+            suffix: suffix.to_string(),
+        })
+    }
+}
+
+/// A parsed wheel filename
+///
+/// `{distribution}-{version}(-{build tag})?-{python tag}-{abi tag}-{platform tag}.whl`
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WheelFilename {
+    /// The distribution name as written in the filename, compared after PEP 503 normalization.
+    pub name: PackageName,
+    /// The distribution version.
+    pub version: String,
+    /// Optional build number.
+    pub build_tag: Option<BuildTag>,
+    /// The compressed set of Python tags, e.g. `["py2", "py3"]` for `py2.py3`.
+    pub python_tags: Vec<String>,
+    /// The compressed set of ABI tags.
+    pub abi_tags: Vec<String>,
+    /// The compressed set of platform tags.
+    pub platform_tags: Vec<String>,
+}
+
+impl WheelFilename {
+    /// Returns the expanded set of `{python tag}-{abi tag}-{platform tag}` triples
+    pub fn tags(&self) -> Vec<String> {
+        let mut tags = Vec::new();
+        for python_tag in &self.python_tags {
+            for abi_tag in &self.abi_tags {
+                for platform_tag in &self.platform_tags {
+                    tags.push(format!("{python_tag}-{abi_tag}-{platform_tag}"));
+                }
+            }
+        }
+        tags
+    }
+}
+
+impl fmt::Display for WheelFilename {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.name, self.version)?;
+        if let Some(build_tag) = &self.build_tag {
+            write!(f, "-{build_tag}")?;
+        }
+        write!(
+            f,
+            "-{}-{}-{}.whl",
+            self.python_tags.join("."),
+            self.abi_tags.join("."),
+            self.platform_tags.join(".")
+        )
+    }
+}
+
+impl FromStr for WheelFilename {
+    type Err = Error;
+
+    fn from_str(filename: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| Error::InvalidWheelFilename {
+            filename: filename.to_string(),
+            reason: reason.to_string(),
+        };
+        let stem = filename
+            .strip_suffix(".whl")
+            .ok_or_else(|| invalid("must end with .whl"))?;
+        let parts: Vec<&str> = stem.split('-').collect();
+        if parts.iter().any(|part| part.is_empty()) {
+            return Err(invalid("must not contain empty components"));
+        }
+        let (name, version, build_tag, python_tag, abi_tag, platform_tag) = match parts.as_slice() {
+            [name, version, python_tag, abi_tag, platform_tag] => {
+                (name, version, None, python_tag, abi_tag, platform_tag)
+            }
+            [name, version, build_tag, python_tag, abi_tag, platform_tag] => {
+                let build_tag = BuildTag::parse(build_tag)
+                    .ok_or_else(|| invalid("build tag must start with a digit"))?;
+                (
+                    name,
+                    version,
+                    Some(build_tag),
+                    python_tag,
+                    abi_tag,
+                    platform_tag,
+                )
+            }
+            _ => return Err(invalid("expected 5 or 6 dash-separated components")),
+        };
+        let split_tags = |tags: &str| -> Result<Vec<String>, Error> {
+            tags.split('.')
+                .map(|tag| {
+                    if tag.is_empty() {
+                        Err(invalid("must not contain empty tags"))
+                    } else {
+                        Ok(tag.to_string())
+                    }
+                })
+                .collect()
+        };
+        let name = PackageName::new(*name).map_err(|_| invalid("invalid distribution name"))?;
+        Ok(Self {
+            name,
+            version: version.to_string(),
+            build_tag,
+            python_tags: split_tags(python_tag)?,
+            abi_tags: split_tags(abi_tag)?,
+            platform_tags: split_tags(platform_tag)?,
+        })
+    }
+}
+
+/// Parsed contents of the `.dist-info/WHEEL` file of a wheel
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WheelInfo {
+    /// Version number of the wheel specification, e.g. `1.0`.
+    pub wheel_version: String,
+    /// Name and optionally version of the tool that generated the wheel.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub generator: Option<String>,
+    /// Whether the archive root should be installed into purelib, otherwise platlib.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub root_is_purelib: bool,
+    /// The expanded `{python tag}-{abi tag}-{platform tag}` triples the wheel supports.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tags: Vec<String>,
+    /// The build number, if any.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub build: Option<String>,
+}
+
+impl WheelInfo {
+    /// Parse the contents of a `WHEEL` file
+    pub fn parse(content: &[u8]) -> Result<Self, Error> {
+        let (headers, _) = mailparse::parse_headers(content)?;
+        let wheel_version = headers
+            .get_first_value("Wheel-Version")
+            .ok_or(Error::FieldNotFound("Wheel-Version"))?;
+        let generator = headers.get_first_value("Generator");
+        // Same as pip, anything but `true` means platlib
+        let root_is_purelib = headers
+            .get_first_value("Root-Is-Purelib")
+            .is_some_and(|value| value.trim().eq_ignore_ascii_case("true"));
+        let tags = headers.get_all_values("Tag");
+        let build = headers.get_first_value("Build");
+        Ok(Self {
+            wheel_version,
+            generator,
+            root_is_purelib,
+            tags,
+            build,
+        })
+    }
+
+    /// Returns the distinct Python tags of [`WheelInfo::tags`], in order of appearance
+    pub fn python_tags(&self) -> Vec<&str> {
+        let mut python_tags = Vec::new();
+        for tag in &self.tags {
+            let python_tag = tag.split('-').next().unwrap_or(tag);
+            if !python_tags.contains(&python_tag) {
+                python_tags.push(python_tag);
+            }
+        }
+        python_tags
+    }
+}
+
+impl FromStr for WheelInfo {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        WheelInfo::parse(s.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BuildTag, WheelFilename, WheelInfo};
+    use crate::Error;
+
+    #[test]
+    fn test_parse_wheel_filename() {
+        let filename: WheelFilename = "build-0.4.0-py2.py3-none-any.whl".parse().unwrap();
+        assert_eq!(filename.name, "build");
+        assert_eq!(filename.version, "0.4.0");
+        assert_eq!(filename.build_tag, None);
+        assert_eq!(filename.python_tags, ["py2", "py3"]);
+        assert_eq!(filename.abi_tags, ["none"]);
+        assert_eq!(filename.platform_tags, ["any"]);
+        assert_eq!(filename.tags(), ["py2-none-any", "py3-none-any"]);
+        assert_eq!(filename.to_string(), "build-0.4.0-py2.py3-none-any.whl");
+
+        let filename: WheelFilename =
+            "foo_bar-1.0-12abc-cp38-abi3-manylinux_2_17_x86_64.manylinux2014_x86_64.whl"
+                .parse()
+                .unwrap();
+        assert_eq!(filename.name, "foo_bar");
+        assert_eq!(
+            filename.build_tag,
+            Some(BuildTag {
+                number: 12,
+                suffix: "abc".to_string()
+            })
+        );
+        assert_eq!(filename.python_tags, ["cp38"]);
+        assert_eq!(filename.abi_tags, ["abi3"]);
+        assert_eq!(
+            filename.platform_tags,
+            ["manylinux_2_17_x86_64", "manylinux2014_x86_64"]
+        );
+        assert_eq!(
+            filename.to_string(),
+            "foo_bar-1.0-12abc-cp38-abi3-manylinux_2_17_x86_64.manylinux2014_x86_64.whl"
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_wheel_filename() {
+        for filename in [
+            "build-0.4.0-py2.py3-none-any.zip",
+            "build-0.4.0-none-any.whl",
+            "build-0.4.0-1-2-py3-none-any.whl",
+            "build-0.4.0-abc-py3-none-any.whl",
+            "build--py3-none-any.whl",
+            "build-0.4.0-py2.-none-any.whl",
+            "build!-0.4.0-py3-none-any.whl",
+        ] {
+            let result: Result<WheelFilename, Error> = filename.parse();
+            assert!(
+                matches!(result, Err(Error::InvalidWheelFilename { .. })),
+                "{filename}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_wheel_info() {
+        let s = "Wheel-Version: 1.0\nGenerator: bdist_wheel (0.36.2)\nRoot-Is-Purelib: true\nTag: py2-none-any\nTag: py3-none-any\n";
+        let info: WheelInfo = s.parse().unwrap();
+        assert_eq!(info.wheel_version, "1.0");
+        assert_eq!(info.generator.as_deref(), Some("bdist_wheel (0.36.2)"));
+        assert!(info.root_is_purelib);
+        assert_eq!(info.tags, ["py2-none-any", "py3-none-any"]);
+        assert_eq!(info.python_tags(), ["py2", "py3"]);
+        assert_eq!(info.build, None);
+
+        let s = "Wheel-Version: 1.0\nRoot-Is-Purelib: false\nTag: cp38-cp38-manylinux1_x86_64\nBuild: 1";
+        let info: WheelInfo = s.parse().unwrap();
+        assert!(!info.root_is_purelib);
+        assert_eq!(info.build.as_deref(), Some("1"));
+
+        let info: Result<WheelInfo, Error> = "Generator: foo".parse();
+        assert!(matches!(info, Err(Error::FieldNotFound("Wheel-Version"))));
+    }
+}
@@ -1,4 +1,6 @@
-use python_pkginfo::{Distribution, DistributionType};
+use python_pkginfo::{
+    Distribution, DistributionType, Error, Metadata, RecordEntry, RecordMismatch, Version,
+};
 
 #[test]
 fn test_parse_wheel() {
@@ -10,6 +12,72 @@ fn test_parse_wheel() {
     assert!(metadata.home_page.is_none());
     assert!(metadata.download_url.is_none());
     assert_eq!(dist.python_version(), "py2.py3");
+    assert_eq!(dist.normalized_name(), "build");
+    assert_eq!(metadata.package_name().unwrap(), "Build");
+    let requirements = metadata.requirements().unwrap();
+    assert_eq!(requirements.len(), 19);
+    assert_eq!(requirements[3].to_string(), "colorama; os_name == \"nt\"");
+    let wheel_filename = dist.wheel_filename().unwrap();
+    assert_eq!(wheel_filename.name, "build");
+    assert_eq!(wheel_filename.version, "0.4.0");
+    assert_eq!(wheel_filename.python_tags, ["py2", "py3"]);
+    assert_eq!(wheel_filename.abi_tags, ["none"]);
+    assert_eq!(wheel_filename.platform_tags, ["any"]);
+    let wheel_info = dist.wheel_info().unwrap();
+    assert_eq!(wheel_info.wheel_version, "1.0");
+    assert_eq!(
+        wheel_info.generator.as_deref(),
+        Some("bdist_wheel (0.36.2)")
+    );
+    assert!(wheel_info.root_is_purelib);
+    assert_eq!(wheel_info.tags, ["py2-none-any", "py3-none-any"]);
+    let entry_points = dist.entry_points().unwrap();
+    assert_eq!(entry_points.groups(), ["console_scripts", "pipx.run"]);
+    let scripts: Vec<_> = entry_points.group("console_scripts").collect();
+    assert_eq!(scripts.len(), 1);
+    assert_eq!(scripts[0].name, "pyproject-build");
+    assert_eq!(scripts[0].value(), "build.__main__:entrypoint");
+}
+
+#[test]
+fn test_supports_python() {
+    let dist = Distribution::new("tests/fixtures/build-0.4.0-py2.py3-none-any.whl").unwrap();
+    for (python_version, expected) in [("2.7", true), ("3.4", false), ("3.12", true)] {
+        let python_version: Version = python_version.parse().unwrap();
+        assert_eq!(dist.supports_python(&python_version).unwrap(), expected);
+    }
+
+    // `py3` wheel tag
+    let dist = Distribution::new("tests/fixtures/lenient-1.0-py3-none-any.whl").unwrap();
+    for (python_version, expected) in [("2.7", false), ("3.12", true)] {
+        let python_version: Version = python_version.parse().unwrap();
+        assert_eq!(dist.supports_python(&python_version).unwrap(), expected);
+    }
+
+    // Eggs are built for a single Python version
+    let dist = Distribution::new("tests/fixtures/build-0.4.0-py3.9.egg").unwrap();
+    for (python_version, expected) in [("3.8", false), ("3.9.1", true), ("3.10", false)] {
+        let python_version: Version = python_version.parse().unwrap();
+        assert_eq!(dist.supports_python(&python_version).unwrap(), expected);
+    }
+}
+
+#[test]
+fn test_parse_wheel_invalid_filename() {
+    // A renamed wheel is still read, the Python tags come from its `WHEEL` file
+    let bytes = std::fs::read("tests/fixtures/build-0.4.0-py2.py3-none-any.whl").unwrap();
+    let dist = Distribution::from_reader(std::io::Cursor::new(bytes), "build.whl").unwrap();
+    assert_eq!(dist.r#type(), DistributionType::Wheel);
+    assert_eq!(dist.metadata().name, "build");
+    assert!(dist.wheel_filename().is_none());
+    assert_eq!(dist.python_version(), "py2.py3");
+
+    let bytes = std::fs::read("tests/fixtures/build-0.4.0-py2.py3-none-any.whl.metadata").unwrap();
+    let dist =
+        Distribution::from_reader(std::io::Cursor::new(bytes), "build.whl.metadata").unwrap();
+    assert_eq!(dist.r#type(), DistributionType::Wheel);
+    assert!(dist.wheel_filename().is_none());
+    assert_eq!(dist.python_version(), "any");
 }
 
 #[test]
@@ -32,6 +100,40 @@ fn test_parse_egg() {
     assert!(metadata.home_page.is_none());
     assert!(metadata.download_url.is_none());
     assert_eq!(dist.python_version(), "py3.9");
+    let entry_points = dist.entry_points().unwrap();
+    assert_eq!(entry_points.groups(), ["console_scripts", "pipx.run"]);
+    let egg_filename = dist.egg_filename().unwrap();
+    assert_eq!(egg_filename.name, "build");
+    assert_eq!(egg_filename.version, "0.4.0");
+    assert_eq!(egg_filename.pyver.as_deref(), Some("3.9"));
+    assert_eq!(egg_filename.platform, None);
+    let requirements = dist.egg_requirements().unwrap();
+    assert_eq!(requirements.len(), 19);
+    assert_eq!(requirements[0].to_string(), "packaging>=19.0");
+    assert_eq!(requirements[3].to_string(), "colorama; os_name == \"nt\"");
+    assert_eq!(
+        requirements[8].to_string(),
+        "sphinx~=3.0; extra == \"docs\""
+    );
+
+    // Invalid requirements like `foo==dev` are skipped and a malformed `depends.txt` is ignored
+    let dist = Distribution::new("tests/fixtures/legacy-1.0-py2.7.egg").unwrap();
+    assert_eq!(dist.metadata().name, "legacy");
+    let requirements: Vec<_> = dist
+        .egg_requirements()
+        .unwrap()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(requirements, ["six", "mock; extra == \"test\""]);
+
+    // A renamed egg is still read, only without a parsed filename
+    let bytes = std::fs::read("tests/fixtures/build-0.4.0-py3.9.egg").unwrap();
+    let dist = Distribution::from_reader(std::io::Cursor::new(bytes), "build.egg").unwrap();
+    assert_eq!(dist.r#type(), DistributionType::Egg);
+    assert_eq!(dist.metadata().name, "build");
+    assert!(dist.egg_filename().is_none());
+    assert_eq!(dist.python_version(), "any");
 }
 
 #[test]
@@ -107,3 +209,399 @@ fn test_parse_sdist_tar_xz() {
     assert!(metadata.home_page.is_none());
     assert!(metadata.download_url.is_none());
 }
+
+#[test]
+fn test_parse_wheel_from_reader() {
+    let bytes = std::fs::read("tests/fixtures/build-0.4.0-py2.py3-none-any.whl").unwrap();
+    let dist = Distribution::from_reader(
+        std::io::Cursor::new(bytes),
+        "build-0.4.0-py2.py3-none-any.whl",
+    )
+    .unwrap();
+    assert_eq!(dist.r#type(), DistributionType::Wheel);
+    assert_eq!(dist.metadata().name, "build");
+    assert_eq!(dist.python_version(), "py2.py3");
+}
+
+#[test]
+fn test_parse_sdist_tar_gz_metadata_file_order() {
+    let metadata = |name: &str| format!("Metadata-Version: 2.1\nName: {name}\nVersion: 1.0\n");
+    // `tar -czf foo-1.0.tar.gz ./foo-1.0` adds a leading `./`
+    for prefix in ["", "./"] {
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ));
+        // The vendored and egg-info copies come before the top-level `PKG-INFO`
+        for (path, name) in [
+            ("foo-1.0/vendor/bar-2.0/PKG-INFO", "bar"),
+            ("foo-1.0/src/foo.egg-info/PKG-INFO", "foo-egg-info"),
+            ("foo-1.0/PKG-INFO", "foo"),
+        ]
+        .into_iter()
+        .map(|(path, name)| (format!("{prefix}{path}"), name))
+        {
+            let content = metadata(name);
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            // `set_path` would strip the leading `./`
+            header.as_old_mut().name[..path.len()].copy_from_slice(path.as_bytes());
+            header.set_cksum();
+            builder.append(&header, content.as_bytes()).unwrap();
+        }
+        let bytes = builder.into_inner().unwrap().finish().unwrap();
+        let dist =
+            Distribution::from_reader(std::io::Cursor::new(bytes), "foo-1.0.tar.gz").unwrap();
+        assert_eq!(dist.metadata().name, "foo", "{prefix}");
+    }
+}
+
+#[test]
+fn test_parse_sdist_tar_gz_from_reader() {
+    let bytes = std::fs::read("tests/fixtures/build-0.4.0.tar.gz").unwrap();
+    let dist =
+        Distribution::from_reader(std::io::Cursor::new(bytes), "build-0.4.0.tar.gz").unwrap();
+    assert_eq!(dist.r#type(), DistributionType::SDist);
+    assert_eq!(dist.metadata().name, "build");
+    assert_eq!(dist.python_version(), "source");
+}
+
+#[test]
+fn test_parse_from_stream() {
+    // `&[u8]` implements `Read` but not `Seek`
+    let bytes = std::fs::read("tests/fixtures/build-0.4.0.tar.gz").unwrap();
+    let dist = Distribution::from_stream(bytes.as_slice(), "build-0.4.0.tar.gz").unwrap();
+    assert_eq!(dist.r#type(), DistributionType::SDist);
+    assert_eq!(dist.metadata().name, "build");
+    assert_eq!(dist.filename(), Some("build-0.4.0.tar.gz"));
+
+    let bytes = std::fs::read("tests/fixtures/build-0.4.0-py2.py3-none-any.whl").unwrap();
+    let dist =
+        Distribution::from_stream(bytes.as_slice(), "build-0.4.0-py2.py3-none-any.whl").unwrap();
+    assert_eq!(dist.r#type(), DistributionType::Wheel);
+    assert_eq!(dist.python_version(), "py2.py3");
+
+    let bytes = std::fs::read("tests/fixtures/build-0.4.0.zip").unwrap();
+    let dist = Distribution::from_stream(bytes.as_slice(), "build-0.4.0.zip").unwrap();
+    assert_eq!(dist.r#type(), DistributionType::SDist);
+    assert_eq!(dist.metadata().name, "build");
+}
+
+#[test]
+fn test_detect_wheel_by_content() {
+    let bytes = std::fs::read("tests/fixtures/build-0.4.0-py2.py3-none-any.whl").unwrap();
+    let dist = Distribution::from_reader(std::io::Cursor::new(bytes), "download.tmp").unwrap();
+    assert_eq!(dist.r#type(), DistributionType::Wheel);
+    assert_eq!(dist.metadata().name, "build");
+    // Without a wheel filename the Python tags come from the `WHEEL` file
+    assert!(dist.wheel_filename().is_none());
+    assert_eq!(dist.python_version(), "py2.py3");
+}
+
+#[test]
+fn test_detect_egg_by_content() {
+    let bytes = std::fs::read("tests/fixtures/build-0.4.0-py3.9.egg").unwrap();
+    let dist = Distribution::from_reader(std::io::Cursor::new(bytes), "download").unwrap();
+    assert_eq!(dist.r#type(), DistributionType::Egg);
+    assert_eq!(dist.metadata().name, "build");
+}
+
+#[test]
+fn test_detect_sdist_by_content() {
+    // A zipped sdist with a misleading extension
+    let bytes = std::fs::read("tests/fixtures/build-0.4.0.zip").unwrap();
+    let dist =
+        Distribution::from_reader(std::io::Cursor::new(bytes), "build-0.4.0.tar.gz").unwrap();
+    assert_eq!(dist.r#type(), DistributionType::SDist);
+    assert_eq!(dist.metadata().name, "build");
+
+    let bytes = std::fs::read("tests/fixtures/build-0.4.0.tar.gz").unwrap();
+    let dist = Distribution::from_reader(std::io::Cursor::new(bytes), "build-0.4.0.zip").unwrap();
+    assert_eq!(dist.r#type(), DistributionType::SDist);
+    assert_eq!(dist.metadata().name, "build");
+}
+
+#[test]
+fn test_unknown_distribution_type() {
+    let dist = Distribution::from_reader(std::io::Cursor::new(b"not an archive"), "foo.txt");
+    assert!(matches!(dist, Err(Error::UnknownDistributionType)));
+}
+
+#[test]
+fn test_verify_record() {
+    let dist = Distribution::new("tests/fixtures/build-0.4.0-py2.py3-none-any.whl").unwrap();
+    let record = dist.record().unwrap();
+    assert!(record
+        .entries
+        .iter()
+        .any(|entry| entry.path == "build-0.4.0.dist-info/RECORD" && entry.hash.is_none()));
+    assert!(dist.record_verification().is_none());
+
+    let dist =
+        Distribution::new_verified("tests/fixtures/build-0.4.0-py2.py3-none-any.whl").unwrap();
+    let verification = dist.record_verification().unwrap();
+    assert!(verification.is_ok(), "{verification:?}");
+
+    let bytes = std::fs::read("tests/fixtures/py-1.11.0-py2.py3-none-any.whl").unwrap();
+    let dist = Distribution::from_reader_verified(
+        std::io::Cursor::new(&bytes),
+        "py-1.11.0-py2.py3-none-any.whl",
+    )
+    .unwrap();
+    let verification = dist.record_verification().unwrap();
+    assert!(verification.is_ok(), "{verification:?}");
+
+    // Malformed `RECORD` files are only ignored when not verifying
+    let result = Distribution::new_verified("tests/fixtures/lenient-1.0-py3-none-any.whl");
+    assert!(matches!(result, Err(Error::InvalidRecord { line: 1, .. })));
+    let dist = Distribution::new("tests/fixtures/norecord-1.0-py3-none-any.whl").unwrap();
+    assert!(dist.record().is_none());
+    let result = Distribution::new_verified("tests/fixtures/norecord-1.0-py3-none-any.whl");
+    assert!(matches!(result, Err(Error::RecordNotFound)));
+
+    // Sdists have no `RECORD` file to verify
+    let dist = Distribution::new_verified("tests/fixtures/build-0.4.0.tar.gz").unwrap();
+    assert!(dist.record_verification().is_none());
+}
+
+#[test]
+fn test_verify_record_mismatch() {
+    let bytes = std::fs::read("tests/fixtures/build-0.4.0-py2.py3-none-any.whl").unwrap();
+    let dist = Distribution::from_reader(
+        std::io::Cursor::new(&bytes),
+        "build-0.4.0-py2.py3-none-any.whl",
+    )
+    .unwrap();
+
+    let mut record = dist.record().unwrap().clone();
+    // Drop `build/__init__.py`, tamper with `build/__main__.py` and add a file that doesn't exist
+    let removed = record.entries.remove(0);
+    record.entries[0].hash = Some("sha256=AAAA".to_string());
+    record.entries[0].size = Some(2);
+    record.entries[1].size = Some(1);
+    record.entries.push(RecordEntry {
+        path: "build/missing.py".to_string(),
+        hash: Some("sha256=AAAA".to_string()),
+        size: Some(0),
+    });
+    let verification = record.verify(std::io::Cursor::new(&bytes)).unwrap();
+    assert!(!verification.is_ok());
+    assert_eq!(verification.missing, ["build/missing.py"]);
+    assert_eq!(verification.extra, [removed.path]);
+    assert!(matches!(
+        &verification.mismatched[..],
+        [
+            RecordMismatch::Hash { path, expected, .. },
+            RecordMismatch::Size { expected: 2, .. },
+            RecordMismatch::Size { expected: 1, .. },
+        ] if path == "build/__main__.py" && expected == "sha256=AAAA"
+    ));
+}
+
+#[test]
+fn test_metadata_round_trip() {
+    for path in [
+        "tests/fixtures/build-0.4.0-py2.py3-none-any.whl",
+        "tests/fixtures/build-0.4.0-py3.9.egg",
+        "tests/fixtures/build-0.4.0.tar.gz",
+        "tests/fixtures/py-1.11.0-py2.py3-none-any.whl",
+    ] {
+        let dist = Distribution::new(path).unwrap();
+        let metadata: Metadata = dist.metadata().to_string().parse().unwrap();
+        assert_eq!(&metadata, dist.metadata(), "{path}");
+    }
+}
+
+#[test]
+fn test_check_filename() {
+    // Directories have no filename to check
+    let dist = Distribution::new("tests/fixtures/build-0.4.0").unwrap();
+    assert!(dist.filename().is_none());
+    assert!(dist.check_filename().is_ok());
+
+    for path in [
+        "tests/fixtures/build-0.4.0-py2.py3-none-any.whl",
+        "tests/fixtures/build-0.4.0-py3.9.egg",
+        "tests/fixtures/build-0.4.0.tar.gz",
+        "tests/fixtures/build-0.4.0.zip",
+        "tests/fixtures/py-1.11.0-py2.py3-none-any.whl",
+    ] {
+        let dist = Distribution::new(path).unwrap();
+        dist.check_filename().unwrap();
+    }
+
+    for (path, filename, expected_field) in [
+        (
+            "tests/fixtures/build-0.4.0-py2.py3-none-any.whl",
+            "Build-0.4.0-py3-none-any.whl",
+            None,
+        ),
+        (
+            "tests/fixtures/build-0.4.0-py2.py3-none-any.whl",
+            "foo-0.4.0-py3-none-any.whl",
+            Some("name"),
+        ),
+        (
+            "tests/fixtures/build-0.4.0-py2.py3-none-any.whl",
+            "build-0.5.0-py3-none-any.whl",
+            Some("version"),
+        ),
+        (
+            "tests/fixtures/build-0.4.0-py3.9.egg",
+            "foo-0.4.0-py3.9.egg",
+            Some("name"),
+        ),
+        (
+            "tests/fixtures/build-0.4.0.tar.gz",
+            "build-0.4.tar.gz",
+            None,
+        ),
+        (
+            "tests/fixtures/build-0.4.0.tar.gz",
+            "build-0.4.0_post0.tar.gz",
+            Some("version"),
+        ),
+        (
+            "tests/fixtures/build-0.4.0.tar.gz",
+            "build-1.0.tar.gz",
+            Some("version"),
+        ),
+    ] {
+        let bytes = std::fs::read(path).unwrap();
+        let dist = Distribution::from_reader(std::io::Cursor::new(bytes), filename).unwrap();
+        assert_eq!(dist.filename(), Some(filename));
+        match (dist.check_filename(), expected_field) {
+            (Ok(()), None) => {}
+            (Err(Error::FilenameMismatch { field, .. }), Some(expected)) => {
+                assert_eq!(field, expected, "{filename}")
+            }
+            (result, _) => panic!("unexpected result for {filename}: {result:?}"),
+        }
+    }
+
+    let bytes = std::fs::read("tests/fixtures/build-0.4.0.tar.gz").unwrap();
+    let dist = Distribution::from_reader(std::io::Cursor::new(bytes), "build.tar.gz").unwrap();
+    assert!(matches!(
+        dist.check_filename(),
+        Err(Error::InvalidFilename { filename, .. }) if filename == "build.tar.gz"
+    ));
+}
+
+#[test]
+fn test_parse_metadata_file() {
+    let dist =
+        Distribution::new("tests/fixtures/build-0.4.0-py2.py3-none-any.whl.metadata").unwrap();
+    assert_eq!(dist.r#type(), DistributionType::Wheel);
+    assert_eq!(dist.metadata().name, "build");
+    assert_eq!(dist.python_version(), "py2.py3");
+    assert_eq!(dist.wheel_filename().unwrap().version, "0.4.0");
+    dist.check_filename().unwrap();
+
+    let hash = "sha256=03ae68d5e5b39dbc6db958136e939bd7d26622b7156f0b8ceadb674d324afa03";
+    assert!(dist.verify_metadata_hash(hash).unwrap());
+    assert!(dist
+        .verify_metadata_hash(&hash.replace("03ae", "03AE"))
+        .unwrap());
+    assert!(!dist
+        .verify_metadata_hash(
+            "sha256=0000000000000000000000000000000000000000000000000000000000000000"
+        )
+        .unwrap());
+    // PEP 714 allows advertising the metadata file without a hash
+    assert!(dist.verify_metadata_hash("true").unwrap());
+    for hash in [
+        "md5=d41d8cd98f00b204e9800998ecf8427e",
+        "sha224=d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f",
+        "blake2b=00",
+    ] {
+        assert!(matches!(
+            dist.verify_metadata_hash(hash),
+            Err(Error::UnsupportedHashAlgorithm(_))
+        ));
+    }
+    for hash in ["false", "sha256", "foo=00", "sha256=03ae", "sha256=zz"] {
+        assert!(matches!(
+            dist.verify_metadata_hash(hash),
+            Err(Error::InvalidHash(_))
+        ));
+    }
+
+    let bytes = std::fs::read("tests/fixtures/build-0.4.0-py2.py3-none-any.whl.metadata").unwrap();
+    let dist =
+        Distribution::from_reader(std::io::Cursor::new(bytes), "build-0.4.0.tar.gz.metadata")
+            .unwrap();
+    assert_eq!(dist.r#type(), DistributionType::SDist);
+    dist.check_filename().unwrap();
+
+    let dist = Distribution::new("tests/fixtures/build-0.4.0-py2.py3-none-any.whl").unwrap();
+    assert!(matches!(
+        dist.verify_metadata_hash(hash),
+        Err(Error::MetadataNotFound)
+    ));
+}
+
+#[test]
+fn test_parse_sdist_directory() {
+    let dist = Distribution::new("tests/fixtures/build-0.4.0").unwrap();
+    assert_eq!(dist.r#type(), DistributionType::SDist);
+    assert_eq!(dist.python_version(), "source");
+    assert_eq!(dist.filename(), None);
+    let metadata = dist.metadata();
+    assert_eq!(metadata.name, "build");
+    // The top-level `PKG-INFO` wins over `src/build.egg-info/PKG-INFO` with version `0.4.0.dev0`
+    assert_eq!(metadata.version, "0.4.0");
+    assert_eq!(metadata.metadata_version, "2.1");
+
+    let dist = Distribution::new("tests/fixtures/develop/foo").unwrap();
+    assert_eq!(dist.metadata().name, "foo");
+}
+
+#[test]
+fn test_parse_pyproject_directory() {
+    let dist = Distribution::new("tests/fixtures/spam").unwrap();
+    assert_eq!(dist.r#type(), DistributionType::SDist);
+    let metadata = dist.metadata();
+    assert_eq!(metadata.name, "spam");
+    assert_eq!(metadata.version, "1.0.0");
+    assert_eq!(
+        metadata.description.as_deref(),
+        Some("# Spam\n\nWonderful spam.\n")
+    );
+    assert_eq!(
+        metadata.description_content_type.as_deref(),
+        Some("text/markdown")
+    );
+    assert_eq!(metadata.requires_dist, ["eggs>=2"]);
+    // `PKG-INFO` files of the virtual environment and vendored packages are ignored
+    assert!(std::path::Path::new(
+        "tests/fixtures/spam/venv/lib/python3.12/site-packages/six-1.16.0.egg-info/PKG-INFO"
+    )
+    .is_file());
+    assert!(std::path::Path::new("tests/fixtures/spam/vendor/bar/PKG-INFO").is_file());
+
+    let result = Distribution::new("tests/fixtures/pth-target");
+    assert!(matches!(result, Err(Error::MetadataNotFound)));
+}
+
+#[test]
+fn test_parse_wheel_with_malformed_files() {
+    // `WHEEL` lacks the required `Wheel-Version` and `RECORD` has an invalid size
+    let dist = Distribution::new("tests/fixtures/lenient-1.0-py3-none-any.whl").unwrap();
+    assert_eq!(dist.metadata().name, "lenient");
+    assert!(dist.wheel_info().is_none());
+    assert!(dist.record().is_none());
+    assert_eq!(dist.python_version(), "py3");
+    // `entry_points.txt` has a continuation line and a module name that isn't a dotted path
+    let scripts: Vec<_> = dist
+        .entry_points()
+        .unwrap()
+        .entries
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(
+        scripts,
+        ["lenient = lenient:main", "lenient-gui = lenient-gui:main"]
+    );
+}
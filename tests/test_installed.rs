@@ -0,0 +1,123 @@
+use python_pkginfo::{Error, InstalledDistribution, InstalledType};
+
+#[test]
+fn test_open_dist_info() {
+    let dist =
+        InstalledDistribution::new("tests/fixtures/site-packages/build-0.4.0.dist-info").unwrap();
+    assert_eq!(dist.r#type(), InstalledType::DistInfo);
+    assert_eq!(dist.metadata().name, "build");
+    assert_eq!(dist.metadata().version, "0.4.0");
+    assert_eq!(
+        dist.wheel_info().unwrap().tags,
+        ["py2-none-any", "py3-none-any"]
+    );
+    assert_eq!(dist.record().unwrap().entries.len(), 11);
+    let scripts: Vec<_> = dist
+        .entry_points()
+        .unwrap()
+        .group("console_scripts")
+        .collect();
+    assert_eq!(scripts[0].name, "pyproject-build");
+    assert_eq!(dist.installer(), Some("pip"));
+    assert!(dist.direct_url().unwrap().contains("archive_info"));
+    assert!(dist.requested());
+}
+
+#[test]
+fn test_open_egg_info() {
+    let dist = InstalledDistribution::new("tests/fixtures/site-packages/py-1.11.0-py3.9.egg-info")
+        .unwrap();
+    assert_eq!(dist.r#type(), InstalledType::EggInfo);
+    assert_eq!(dist.metadata().name, "py");
+    assert!(dist.record().is_none());
+    assert!(dist.entry_points().is_none());
+    assert!(dist.installer().is_none());
+    assert!(!dist.requested());
+    assert!(dist.egg_requirements().is_none());
+
+    let dist = InstalledDistribution::new("tests/fixtures/develop/foo/foo.egg-info").unwrap();
+    let requirements: Vec<_> = dist
+        .egg_requirements()
+        .unwrap()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(
+        requirements,
+        [
+            "six",
+            "mock; python_version < \"3.8\" and extra == \"test\""
+        ]
+    );
+
+    // A legacy `requires.txt` doesn't prevent reading the egg-info
+    let dist =
+        InstalledDistribution::new("tests/fixtures/legacy/legacy-1.0-py2.7.egg-info").unwrap();
+    assert_eq!(dist.metadata().name, "legacy");
+    let requirements: Vec<_> = dist
+        .egg_requirements()
+        .unwrap()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(requirements, ["six", "mock; extra == \"test\""]);
+}
+
+#[test]
+fn test_open_metadata_file() {
+    let dist = InstalledDistribution::new(
+        "tests/fixtures/site-packages/py-1.11.0-py3.9.egg-info/PKG-INFO",
+    )
+    .unwrap();
+    assert_eq!(dist.r#type(), InstalledType::MetadataFile);
+    assert_eq!(dist.metadata().version, "1.11.0");
+}
+
+#[test]
+fn test_open_missing_metadata() {
+    let result = InstalledDistribution::new("tests/fixtures/site-packages");
+    assert!(matches!(result, Err(Error::MetadataNotFound)));
+}
+
+#[test]
+fn test_discover() {
+    let dists = InstalledDistribution::discover([
+        "tests/fixtures/site-packages",
+        "tests/fixtures/missing",
+        // Also listed in `extra.pth`, but only searched once
+        "tests/fixtures/pth-target",
+    ]);
+    let found: Vec<_> = dists
+        .iter()
+        .map(|dist| {
+            (
+                dist.metadata().name.as_str(),
+                dist.metadata().version.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        found,
+        [
+            ("build", "0.4.0"),
+            ("foo", "1.0"),
+            ("py", "1.11.0"),
+            ("build", "0.3.0"),
+        ]
+    );
+    assert_eq!(dists[1].r#type(), InstalledType::EggInfo);
+
+    // `broken-1.0.dist-info` without a `Name` and `broken.egg-link`, a directory, are skipped
+    assert!(
+        InstalledDistribution::new("tests/fixtures/site-packages/broken-1.0.dist-info").is_err()
+    );
+}
+
+#[test]
+fn test_open_dist_info_with_malformed_files() {
+    let dist = InstalledDistribution::new("tests/fixtures/lenient/lenient-1.0.dist-info").unwrap();
+    assert_eq!(dist.metadata().name, "lenient");
+    assert!(dist.wheel_info().is_none());
+    assert!(dist.record().is_none());
+    assert_eq!(dist.entry_points().unwrap().entries.len(), 2);
+}